@@ -0,0 +1,486 @@
+use std::{str::FromStr, time::Duration};
+
+use ssal_core::{
+    error::{Error, ErrorCode, WrapError},
+    ethers_core::types::Signature,
+    reqwest::{Client, Url},
+    types::*,
+};
+
+use crate::{sequencer::*, ssal::*, Endpoint, Method};
+
+/// The connection pool shared by every [`SsalClient`] and [`SequencerClient`] built
+/// from it. Cloning is cheap and keeps the pool.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+}
+
+impl HttpClient {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub fn new(timeout: Duration) -> Result<Self, Error> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .wrap("Failed to build the HTTP client")?;
+        Ok(Self { client })
+    }
+
+    /// Send `endpoint` to the server at `base_url`. Failures to reach the server are
+    /// reported as [`ErrorCode::UpstreamFailure`] and error responses are rebuilt
+    /// with [`Error::from_response`].
+    pub async fn request<E>(
+        &self,
+        base_url: &Url,
+        endpoint: &E,
+        admin_key: Option<&str>,
+    ) -> Result<E::Response, Error>
+    where
+        E: Endpoint,
+    {
+        let url = base_url.join(E::PATH.trim_start_matches('/')).wrap_as(
+            ErrorCode::InvalidRequest,
+            format!("[{}]: Failed to parse into URL", E::PATH),
+        )?;
+
+        let mut request = match E::METHOD {
+            Method::Get => self.client.get(url).query(endpoint),
+            Method::Post => self.client.post(url).json(endpoint),
+        };
+        if let Some(admin_key) = admin_key {
+            request = request.header(ADMIN_KEY_HEADER, admin_key);
+        }
+
+        let response = request.send().await.wrap_as(
+            ErrorCode::UpstreamFailure,
+            format!("[{}]: Failed to send a request", E::PATH),
+        )?;
+        if !response.status().is_success() {
+            return Err(Error::from_response(response).await);
+        }
+
+        let body = response.bytes().await.wrap_as(
+            ErrorCode::UpstreamFailure,
+            format!("[{}]: Failed to read the response", E::PATH),
+        )?;
+        E::parse_response(&body)
+    }
+}
+
+/// Paths are joined onto the base URL, so it must end with a slash to keep its own path.
+fn into_base_url(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url
+}
+
+#[derive(Clone)]
+pub struct SsalClient {
+    base_url: Url,
+    http_client: HttpClient,
+    admin_key: Option<String>,
+}
+
+impl SsalClient {
+    pub fn new(base_url: Url, http_client: HttpClient) -> Self {
+        Self {
+            base_url: into_base_url(base_url),
+            http_client,
+            admin_key: None,
+        }
+    }
+
+    /// Sign admin requests with `admin_key`.
+    pub fn with_admin_key(mut self, admin_key: impl Into<String>) -> Self {
+        self.admin_key = Some(admin_key.into());
+        self
+    }
+
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    pub async fn request<E>(&self, endpoint: &E) -> Result<E::Response, Error>
+    where
+        E: Endpoint,
+    {
+        self.http_client
+            .request(&self.base_url, endpoint, self.admin_key.as_deref())
+            .await
+    }
+
+    pub async fn register_rollup(
+        &self,
+        rollup_id: &RollupId,
+        rollup_config: &RollupConfig,
+    ) -> Result<(), Error> {
+        self.request(&RegisterRollup {
+            rollup_id: rollup_id.clone(),
+            election_method: rollup_config.election_method(),
+            commitment_method: rollup_config.commitment_method(),
+            curve: rollup_config.curve(),
+            durability_mode: rollup_config.durability_mode(),
+        })
+        .await
+    }
+
+    /// Requires the client to be built with [`SsalClient::with_admin_key`].
+    pub async fn pause_rollup(&self, rollup_id: &RollupId) -> Result<(), Error> {
+        self.request(&PauseRollup {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    /// Requires the client to be built with [`SsalClient::with_admin_key`].
+    pub async fn resume_rollup(&self, rollup_id: &RollupId) -> Result<(), Error> {
+        self.request(&ResumeRollup {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    /// Requires the client to be built with [`SsalClient::with_admin_key`].
+    pub async fn deregister_rollup(&self, rollup_id: &RollupId) -> Result<(), Error> {
+        self.request(&DeregisterRollup {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_rollup_state(&self, rollup_id: &RollupId) -> Result<RollupState, Error> {
+        self.request(&GetRollupState {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_rollup_config(&self, rollup_id: &RollupId) -> Result<RollupConfig, Error> {
+        self.request(&GetRollupConfig {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_block_height(&self, rollup_id: &RollupId) -> Result<BlockHeight, Error> {
+        self.request(&GetBlockHeight {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    /// `signature` is over [`RegisterSequencer::digest`].
+    pub async fn register_sequencer(
+        &self,
+        rollup_id: &RollupId,
+        sequencer_id: &SequencerId,
+        stake: &Stake,
+        block_height: &BlockHeight,
+        signature: &Signature,
+    ) -> Result<BlockHeight, Error> {
+        self.request(&RegisterSequencer {
+            rollup_id: rollup_id.clone(),
+            sequencer_id: sequencer_id.clone(),
+            stake: stake.clone(),
+            block_height: block_height.clone(),
+            signature: *signature,
+        })
+        .await
+    }
+
+    /// `signature` is over [`DeregisterSequencer::digest`].
+    pub async fn deregister_sequencer(
+        &self,
+        rollup_id: &RollupId,
+        sequencer_id: &SequencerId,
+        block_height: &BlockHeight,
+        signature: &Signature,
+    ) -> Result<BlockHeight, Error> {
+        self.request(&DeregisterSequencer {
+            rollup_id: rollup_id.clone(),
+            sequencer_id: sequencer_id.clone(),
+            block_height: block_height.clone(),
+            signature: *signature,
+        })
+        .await
+    }
+
+    /// Requires the client to be built with [`SsalClient::with_admin_key`].
+    pub async fn exclude_sequencer(
+        &self,
+        rollup_id: &RollupId,
+        sequencer_id: &SequencerId,
+        reason: impl Into<String>,
+        block_count: Option<usize>,
+    ) -> Result<(), Error> {
+        self.request(&ExcludeSequencer {
+            rollup_id: rollup_id.clone(),
+            sequencer_id: sequencer_id.clone(),
+            reason: reason.into(),
+            block_count,
+        })
+        .await
+    }
+
+    pub async fn get_exclusion_list(&self, rollup_id: &RollupId) -> Result<ExclusionList, Error> {
+        self.request(&GetExclusionList {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    pub async fn close_block(&self, rollup_id: &RollupId) -> Result<SequencerId, Error> {
+        self.request(&CloseBlock {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_sequencer_set(&self, rollup_id: &RollupId) -> Result<SequencerSet, Error> {
+        self.request(&GetSequencerSet {
+            rollup_id: rollup_id.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_closed_sequencer_set(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+    ) -> Result<SequencerSet, Error> {
+        self.request(&GetClosedSequencerSet {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+        })
+        .await
+    }
+
+    /// `signature` is over [`ReportLeaderFailure::digest`].
+    pub async fn report_leader_failure(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        reporter_id: &SequencerId,
+        leader_id: &SequencerId,
+        signature: &Signature,
+    ) -> Result<SequencerId, Error> {
+        self.request(&ReportLeaderFailure {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+            reporter_id: reporter_id.clone(),
+            leader_id: leader_id.clone(),
+            signature: *signature,
+        })
+        .await
+    }
+
+    /// `signature` is over [`SubmitBlockCommitment::digest`].
+    pub async fn submit_block_commitment(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        sequencer_id: &SequencerId,
+        block_commitment: &BlockCommitment,
+        signature: &Signature,
+    ) -> Result<(), Error> {
+        self.request(&SubmitBlockCommitment {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+            sequencer_id: sequencer_id.clone(),
+            block_commitment: block_commitment.clone(),
+            signature: *signature,
+        })
+        .await
+    }
+
+    pub async fn get_endorsed_block_commitment(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+    ) -> Result<EndorsedBlockCommitment, Error> {
+        self.request(&GetEndorsedBlockCommitment {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+        })
+        .await
+    }
+}
+
+#[derive(Clone)]
+pub struct SequencerClient {
+    base_url: Url,
+    http_client: HttpClient,
+}
+
+impl SequencerClient {
+    pub fn new(base_url: Url, http_client: HttpClient) -> Self {
+        Self {
+            base_url: into_base_url(base_url),
+            http_client,
+        }
+    }
+
+    /// Sequencers are identified by the URL they serve at.
+    pub fn from_sequencer_id(
+        sequencer_id: &SequencerId,
+        http_client: HttpClient,
+    ) -> Result<Self, Error> {
+        let base_url = Url::from_str(sequencer_id.as_ref()).wrap_as(
+            ErrorCode::InvalidRequest,
+            format!("Failed to parse {:?} into URL", sequencer_id),
+        )?;
+        Ok(Self::new(base_url, http_client))
+    }
+
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    pub async fn request<E>(&self, endpoint: &E) -> Result<E::Response, Error>
+    where
+        E: Endpoint,
+    {
+        self.http_client
+            .request(&self.base_url, endpoint, None)
+            .await
+    }
+
+    pub async fn send_transaction(
+        &self,
+        rollup_id: &RollupId,
+        raw_tx: &RawTransaction,
+    ) -> Result<OrderCommitment, Error> {
+        self.request(&SendTransaction {
+            rollup_id: rollup_id.clone(),
+            raw_tx: raw_tx.clone(),
+            forwarded: false,
+        })
+        .await
+    }
+
+    /// Forward the transaction of a follower to the leader.
+    pub async fn forward_transaction(
+        &self,
+        rollup_id: &RollupId,
+        raw_tx: &RawTransaction,
+    ) -> Result<OrderCommitment, Error> {
+        self.request(&SendTransaction {
+            rollup_id: rollup_id.clone(),
+            raw_tx: raw_tx.clone(),
+            forwarded: true,
+        })
+        .await
+    }
+
+    pub async fn send_transactions(
+        &self,
+        rollup_id: &RollupId,
+        raw_txs: &[RawTransaction],
+    ) -> Result<Vec<OrderCommitment>, Error> {
+        self.request(&SendTransactions {
+            rollup_id: rollup_id.clone(),
+            raw_txs: raw_txs.to_vec(),
+            forwarded: false,
+        })
+        .await
+    }
+
+    /// Forward the batch of a follower to the leader.
+    pub async fn forward_transactions(
+        &self,
+        rollup_id: &RollupId,
+        raw_txs: &[RawTransaction],
+    ) -> Result<Vec<OrderCommitment>, Error> {
+        self.request(&SendTransactions {
+            rollup_id: rollup_id.clone(),
+            raw_txs: raw_txs.to_vec(),
+            forwarded: true,
+        })
+        .await
+    }
+
+    pub async fn sync_transaction(
+        &self,
+        rollup_id: &RollupId,
+        raw_tx: &RawTransaction,
+        order_commitment: &OrderCommitment,
+    ) -> Result<(), Error> {
+        self.request(&SyncTransaction {
+            rollup_id: rollup_id.clone(),
+            block_height: order_commitment.block_height().clone(),
+            tx_order: order_commitment.tx_order().clone(),
+            raw_tx: raw_tx.clone(),
+            order_commitment: order_commitment.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_block(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+    ) -> Result<Vec<RawTransaction>, Error> {
+        self.request(&GetBlock {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_transactions(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        from_tx_order: &TransactionOrder,
+    ) -> Result<Vec<SequencedTransaction>, Error> {
+        self.request(&GetTransactions {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+            from_tx_order: from_tx_order.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_blocks(
+        &self,
+        rollup_id: &RollupId,
+        from_block_height: &BlockHeight,
+        to_block_height: &BlockHeight,
+    ) -> Result<Vec<CommittedBlock>, Error> {
+        self.request(&GetBlocks {
+            rollup_id: rollup_id.clone(),
+            from_block_height: from_block_height.clone(),
+            to_block_height: to_block_height.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_inclusion_proof(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        tx_order: &TransactionOrder,
+    ) -> Result<InclusionProof, Error> {
+        self.request(&GetInclusionProof {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+            tx_order: tx_order.clone(),
+        })
+        .await
+    }
+
+    pub async fn get_block_commitment(
+        &self,
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+    ) -> Result<BlockCommitment, Error> {
+        self.request(&GetBlockCommitment {
+            rollup_id: rollup_id.clone(),
+            block_height: block_height.clone(),
+        })
+        .await
+    }
+}
@@ -0,0 +1,63 @@
+pub mod client;
+pub mod sequencer;
+pub mod ssal;
+
+use ssal_core::{
+    error::{Error, ErrorCode, WrapError},
+    serde::{de::DeserializeOwned, Serialize},
+    serde_json,
+};
+
+pub use self::client::{HttpClient, SequencerClient, SsalClient};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// The request is sent as the query string.
+    Get,
+    /// The request is sent as the JSON body.
+    Post,
+}
+
+/// A route of the SSAL or the sequencer, implemented by its request type.
+pub trait Endpoint: Serialize {
+    const METHOD: Method;
+    /// The route as registered in the axum router.
+    const PATH: &'static str;
+    type Response;
+
+    fn parse_response(body: &[u8]) -> Result<Self::Response, Error>;
+}
+
+/// Parse a JSON response body. An empty body is parsed as `null` so that handlers
+/// returning `()` map to `Response = ()`.
+pub(crate) fn parse_json<T>(body: &[u8]) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let body = match body.is_empty() {
+        true => b"null".as_slice(),
+        false => body,
+    };
+    serde_json::from_slice(body).wrap_as(
+        ErrorCode::UpstreamFailure,
+        format!(
+            "Failed to parse the response into type: {}",
+            std::any::type_name::<T>(),
+        ),
+    )
+}
+
+macro_rules! impl_json_endpoint {
+    ($endpoint:ty, $method:ident, $path:literal, $response:ty) => {
+        impl $crate::Endpoint for $endpoint {
+            const METHOD: $crate::Method = $crate::Method::$method;
+            const PATH: &'static str = $path;
+            type Response = $response;
+
+            fn parse_response(body: &[u8]) -> Result<Self::Response, Error> {
+                $crate::parse_json(body)
+            }
+        }
+    };
+}
+pub(crate) use impl_json_endpoint;
@@ -0,0 +1,177 @@
+//! Requests served by the sequencer.
+use std::str::FromStr;
+
+use ssal_core::{
+    error::{Error, ErrorCode, WrapError},
+    ethers_core::types::Bytes,
+    serde::{Deserialize, Serialize},
+    types::*,
+};
+
+use crate::{impl_json_endpoint, Endpoint, Method};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct SendTransaction {
+    pub rollup_id: RollupId,
+    pub raw_tx: RawTransaction,
+    /// Set when a follower forwards the transaction to the leader.
+    #[serde(default)]
+    pub forwarded: bool,
+}
+impl_json_endpoint!(SendTransaction, Post, "/send-transaction", OrderCommitment);
+
+/// Sequence the transactions in the given order at contiguous transaction orders of the
+/// same block height, at most [`SendTransactions::MAX_TRANSACTIONS`] at a time.
+/// Resubmitted transactions keep their original order commitment.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct SendTransactions {
+    pub rollup_id: RollupId,
+    pub raw_txs: Vec<RawTransaction>,
+    /// Set when a follower forwards the transactions to the leader.
+    #[serde(default)]
+    pub forwarded: bool,
+}
+impl_json_endpoint!(
+    SendTransactions,
+    Post,
+    "/send-transactions",
+    Vec<OrderCommitment>
+);
+
+impl SendTransactions {
+    pub const MAX_TRANSACTIONS: usize = 1024;
+}
+
+/// A transaction sequenced by the leader. Followers store it at the position the
+/// leader assigned, which must match the order commitment.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct SyncTransaction {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+    pub tx_order: TransactionOrder,
+    pub raw_tx: RawTransaction,
+    /// The commitment the leader signed for the transaction.
+    pub order_commitment: OrderCommitment,
+}
+impl_json_endpoint!(SyncTransaction, Post, "/sync-transaction", ());
+
+/// Transactions sequenced by the leader at contiguous transaction orders of
+/// `block_height`, synced to a follower in one request.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct SyncTransactions {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+    pub transactions: Vec<SequencedTransaction>,
+}
+impl_json_endpoint!(SyncTransactions, Post, "/sync-transactions", ());
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetBlock {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+}
+impl_json_endpoint!(GetBlock, Get, "/get-block", Vec<RawTransaction>);
+
+/// A transaction with the order commitment the leader signed for it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct SequencedTransaction {
+    pub raw_tx: RawTransaction,
+    pub order_commitment: OrderCommitment,
+}
+
+/// Returns the transactions of `block_height` from `from_tx_order` on, at most
+/// [`GetTransactions::MAX_TRANSACTIONS`] at a time, so that a sequencer can catch up
+/// with the transactions it missed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetTransactions {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+    pub from_tx_order: TransactionOrder,
+}
+impl_json_endpoint!(
+    GetTransactions,
+    Get,
+    "/get-transactions",
+    Vec<SequencedTransaction>
+);
+
+impl GetTransactions {
+    pub const MAX_TRANSACTIONS: usize = 1024;
+}
+
+/// A block with its block commitment.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct CommittedBlock {
+    pub block_height: BlockHeight,
+    pub block: Vec<RawTransaction>,
+    pub block_commitment: BlockCommitment,
+}
+
+/// Returns the blocks from `from_block_height` up to but excluding `to_block_height`
+/// which the sequencer has committed to, at most [`GetBlocks::MAX_BLOCKS`] at a time.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetBlocks {
+    pub rollup_id: RollupId,
+    pub from_block_height: BlockHeight,
+    pub to_block_height: BlockHeight,
+}
+impl_json_endpoint!(GetBlocks, Get, "/get-blocks", Vec<CommittedBlock>);
+
+impl GetBlocks {
+    pub const MAX_BLOCKS: usize = 16;
+}
+
+/// Returns the opening of the block commitment at `tx_order`, which can be checked with
+/// `ssal_commitment::verify_inclusion_proof`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetInclusionProof {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+    pub tx_order: TransactionOrder,
+}
+impl_json_endpoint!(
+    GetInclusionProof,
+    Get,
+    "/get-inclusion-proof",
+    InclusionProof
+);
+
+/// The block commitment is served as hex-encoded plain text for the operator, in the
+/// tagged encoding of [`BlockCommitment`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetBlockCommitment {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+}
+
+impl Endpoint for GetBlockCommitment {
+    const METHOD: Method = Method::Get;
+    const PATH: &'static str = "/get-block-commitment";
+    type Response = BlockCommitment;
+
+    fn parse_response(body: &[u8]) -> Result<Self::Response, Error> {
+        let body = std::str::from_utf8(body).wrap_as(
+            ErrorCode::UpstreamFailure,
+            "[GetBlockCommitment]: Failed to parse the response into String",
+        )?;
+        let bytes = Bytes::from_str(body).wrap_as(
+            ErrorCode::UpstreamFailure,
+            "[GetBlockCommitment]: Failed to parse the response into Bytes",
+        )?;
+        BlockCommitment::from_bytes(&bytes).wrap_as(
+            ErrorCode::UpstreamFailure,
+            "[GetBlockCommitment]: Failed to decode the block commitment",
+        )
+    }
+}
@@ -0,0 +1,299 @@
+//! Requests served by the SSAL server.
+use ssal_core::{
+    election::ElectionMethod,
+    error::Error,
+    ethers_core::{
+        abi::{self, Token},
+        types::{Signature, U256},
+        utils::keccak256,
+    },
+    serde::{Deserialize, Serialize},
+    types::*,
+};
+
+use crate::{impl_json_endpoint, Endpoint};
+
+/// The header carrying the admin key of the SSAL server.
+pub const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+/// Add RollupId in the RollupSet and inserts into Database an initial block with height 0.
+/// The initial block returns nothing but signals sequencers that they can join in SequencerPool
+/// for the block 1 of the corresponding rollup. The genesis election seed and the rollup
+/// configuration are stored alongside.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct RegisterRollup {
+    pub rollup_id: RollupId,
+    #[serde(default)]
+    pub election_method: ElectionMethod,
+    #[serde(default)]
+    pub commitment_method: CommitmentMethod,
+    #[serde(default)]
+    pub curve: Curve,
+    #[serde(default)]
+    pub durability_mode: DurabilityMode,
+}
+impl_json_endpoint!(RegisterRollup, Post, "/register-rollup", ());
+
+/// Stop accepting blocks and sequencer registrations for the rollup until it is resumed.
+///
+/// The request must carry the admin key of the server in the [`ADMIN_KEY_HEADER`] header.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct PauseRollup {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(PauseRollup, Post, "/pause-rollup", ());
+
+/// Resume a paused rollup.
+///
+/// The request must carry the admin key of the server in the [`ADMIN_KEY_HEADER`] header.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct ResumeRollup {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(ResumeRollup, Post, "/resume-rollup", ());
+
+/// Retire the rollup. Its closed SequencerSets remain queryable through
+/// GetClosedSequencerSet, but no block can be closed for it anymore.
+///
+/// The request must carry the admin key of the server in the [`ADMIN_KEY_HEADER`] header.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct DeregisterRollup {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(DeregisterRollup, Post, "/deregister-rollup", ());
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetRollupState {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(GetRollupState, Get, "/get-rollup-state", RollupState);
+
+/// The settings the rollup was registered with. Sequencers read the commitment method
+/// of their blocks from it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetRollupConfig {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(GetRollupConfig, Get, "/get-rollup-config", RollupConfig);
+
+/// Returns the block height whose SequencerSet is open for registration.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetBlockHeight {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(GetBlockHeight, Get, "/get-block-height", BlockHeight);
+
+/// Register the sequencer in the SequencerSet which is open for registration at
+/// `block_height`. The sequencer is bound to the wallet address which signed
+/// [`RegisterSequencer::digest`], and authenticates its later requests with the same key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct RegisterSequencer {
+    pub rollup_id: RollupId,
+    pub sequencer_id: SequencerId,
+    /// Only used by rollups electing with `ElectionMethod::StakeWeighted`.
+    #[serde(default)]
+    pub stake: Stake,
+    pub block_height: BlockHeight,
+    pub signature: Signature,
+}
+impl_json_endpoint!(RegisterSequencer, Post, "/register-sequencer", BlockHeight);
+
+impl RegisterSequencer {
+    pub fn digest(
+        rollup_id: &RollupId,
+        sequencer_id: &SequencerId,
+        stake: &Stake,
+        block_height: &BlockHeight,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            Token::String(Self::PATH.to_string()),
+            Token::String(rollup_id.to_string()),
+            Token::String(sequencer_id.to_string()),
+            Token::Uint(U256::from(stake.value())),
+            Token::Uint(U256::from(block_height.value())),
+        ]))
+    }
+}
+
+/// Remove the sequencer from the SequencerSet which is open for registration at
+/// `block_height`. Sets that have already been closed are left untouched. The request
+/// must be signed over [`DeregisterSequencer::digest`] with the key the sequencer
+/// registered with.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct DeregisterSequencer {
+    pub rollup_id: RollupId,
+    pub sequencer_id: SequencerId,
+    pub block_height: BlockHeight,
+    pub signature: Signature,
+}
+impl_json_endpoint!(
+    DeregisterSequencer,
+    Post,
+    "/deregister-sequencer",
+    BlockHeight
+);
+
+impl DeregisterSequencer {
+    pub fn digest(
+        rollup_id: &RollupId,
+        sequencer_id: &SequencerId,
+        block_height: &BlockHeight,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            Token::String(Self::PATH.to_string()),
+            Token::String(rollup_id.to_string()),
+            Token::String(sequencer_id.to_string()),
+            Token::Uint(U256::from(block_height.value())),
+        ]))
+    }
+}
+
+/// Admin endpoint which keeps the sequencer out of the SequencerSets of the rollup,
+/// starting from the set that is open for registration. The exclusion lasts for
+/// `block_count` blocks, or permanently when `block_count` is omitted. Earlier exclusions
+/// of the sequencer stay in force, so a permanent exclusion is never shortened.
+///
+/// The request must carry the admin key of the server in the [`ADMIN_KEY_HEADER`] header.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct ExcludeSequencer {
+    pub rollup_id: RollupId,
+    pub sequencer_id: SequencerId,
+    pub reason: String,
+    #[serde(default)]
+    pub block_count: Option<usize>,
+}
+impl_json_endpoint!(ExcludeSequencer, Post, "/exclude-sequencer", ());
+
+/// Returns every exclusion of the rollup with its reason, including the expired ones.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetExclusionList {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(GetExclusionList, Get, "/get-exclusion-list", ExclusionList);
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct CloseBlock {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(CloseBlock, Post, "/close-block", SequencerId);
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetSequencerSet {
+    pub rollup_id: RollupId,
+}
+impl_json_endpoint!(GetSequencerSet, Get, "/get-sequencer-set", SequencerSet);
+
+/// Returns the closed SequencerSet together with the election seed and method so that
+/// the leader can be recomputed with `SequencerSet::verify_leader`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetClosedSequencerSet {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+}
+impl_json_endpoint!(
+    GetClosedSequencerSet,
+    Get,
+    "/get-closed-sequencer-set",
+    SequencerSet
+);
+
+/// Submit the commitment of a built block. The request must be signed over
+/// [`SubmitBlockCommitment::digest`] by `sequencer_id`, a member of the closed
+/// SequencerSet of the block height. The commitment is final once the leader submits it,
+/// or a failover quorum of the followers submits the same one (see
+/// `EndorsedBlockCommitment::is_final`), and is mixed into the election seed two block
+/// heights later, so the leaders of a rollup cannot be predicted from its genesis seed.
+/// Submitting the final commitment again succeeds; a different one is rejected.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct SubmitBlockCommitment {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+    pub sequencer_id: SequencerId,
+    pub block_commitment: BlockCommitment,
+    pub signature: Signature,
+}
+impl_json_endpoint!(SubmitBlockCommitment, Post, "/submit-block-commitment", ());
+
+impl SubmitBlockCommitment {
+    pub fn digest(
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        block_commitment: &BlockCommitment,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            Token::String(Self::PATH.to_string()),
+            Token::String(rollup_id.to_string()),
+            Token::Uint(U256::from(block_height.value())),
+            Token::Bytes(block_commitment.to_bytes()),
+        ]))
+    }
+}
+
+/// Returns the final block commitment together with the signatures which made it final,
+/// so that sequencers recovering the block can check it with
+/// `EndorsedBlockCommitment::is_final` against the closed SequencerSet.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct GetEndorsedBlockCommitment {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+}
+impl_json_endpoint!(
+    GetEndorsedBlockCommitment,
+    Get,
+    "/get-endorsed-block-commitment",
+    EndorsedBlockCommitment
+);
+
+/// Report the leader of a closed SequencerSet as unreachable. Once a quorum of the
+/// followers (see `SequencerSet::failover_quorum`) reports the same leader, a
+/// replacement is elected for the rest of the block height and written back to the
+/// closed SequencerSet. Returns the leader after the report. The request must be signed
+/// over [`ReportLeaderFailure::digest`] with the key the reporter registered with.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct ReportLeaderFailure {
+    pub rollup_id: RollupId,
+    pub block_height: BlockHeight,
+    pub reporter_id: SequencerId,
+    pub leader_id: SequencerId,
+    pub signature: Signature,
+}
+impl_json_endpoint!(
+    ReportLeaderFailure,
+    Post,
+    "/report-leader-failure",
+    SequencerId
+);
+
+impl ReportLeaderFailure {
+    pub fn digest(
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        reporter_id: &SequencerId,
+        leader_id: &SequencerId,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            Token::String(Self::PATH.to_string()),
+            Token::String(rollup_id.to_string()),
+            Token::Uint(U256::from(block_height.value())),
+            Token::String(reporter_id.to_string()),
+            Token::String(leader_id.to_string()),
+        ]))
+    }
+}
@@ -0,0 +1,136 @@
+//! Compare the commitment methods on blocks of 16 to 4096 transactions:
+//!
+//! ```sh
+//! cargo bench -p ssal-commitment
+//! cargo bench -p ssal-commitment --features parallel
+//! ```
+
+use ark_bn254::Bn254;
+use ark_std::test_rng;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ssal_commitment::{
+    param::StructuredReferenceString,
+    srs::{CommitmentParam, CommitmentParamSet},
+    CHUNK_SIZE,
+};
+use ssal_core::types::*;
+
+const BLOCK_SIZES: [usize; 5] = [16, 64, 256, 1024, 4096];
+
+const COMMITMENT_METHODS: [CommitmentMethod; 4] = [
+    CommitmentMethod::VectorCommitment,
+    CommitmentMethod::Kzg,
+    CommitmentMethod::MerkleKeccak256,
+    CommitmentMethod::MerkleSha256,
+];
+
+/// Transactions of the size of a simple transfer.
+fn block(size: usize) -> Vec<RawTransaction> {
+    (0..size)
+        .map(|index| RawTransaction::from(format!("{:0>220}", index)))
+        .collect()
+}
+
+fn commitment(c: &mut Criterion) {
+    let srs_dir = std::env::temp_dir().join(format!("ssal-bench-{}", std::process::id()));
+    let srs = StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(&mut test_rng());
+    let checksum =
+        CommitmentParam::save(&srs, CommitmentParamSet::srs_path(&srs_dir, Curve::Bn254)).unwrap();
+    let params = CommitmentParamSet::new(&srs_dir).with_checksum(Curve::Bn254, checksum);
+    params.load(Curve::Bn254).unwrap();
+
+    let mut commit = c.benchmark_group("commit");
+    commit.sample_size(10);
+    for commitment_method in COMMITMENT_METHODS {
+        for size in BLOCK_SIZES {
+            let block = block(size);
+            commit.bench_with_input(
+                BenchmarkId::new(commitment_method.to_string(), size),
+                &block,
+                |b, block| {
+                    b.iter(|| {
+                        ssal_commitment::get_block_commitment(
+                            &params,
+                            Curve::Bn254,
+                            commitment_method,
+                            block,
+                        )
+                        .unwrap()
+                    })
+                },
+            );
+        }
+    }
+    commit.finish();
+
+    // The last transaction, which is in the last chunk of the block.
+    let mut open = c.benchmark_group("open");
+    open.sample_size(10);
+    for commitment_method in COMMITMENT_METHODS {
+        for size in BLOCK_SIZES {
+            let block = block(size);
+            open.bench_with_input(
+                BenchmarkId::new(commitment_method.to_string(), size),
+                &block,
+                |b, block| {
+                    b.iter(|| {
+                        ssal_commitment::get_inclusion_proof(
+                            &params,
+                            Curve::Bn254,
+                            commitment_method,
+                            block,
+                            BlockHeight::from(1),
+                            TransactionOrder::from(size - 1),
+                        )
+                        .unwrap()
+                    })
+                },
+            );
+        }
+    }
+    open.finish();
+
+    let mut verify = c.benchmark_group("verify");
+    for commitment_method in COMMITMENT_METHODS {
+        for size in BLOCK_SIZES {
+            let block = block(size);
+            let block_commitment = ssal_commitment::get_block_commitment(
+                &params,
+                Curve::Bn254,
+                commitment_method,
+                &block,
+            )
+            .unwrap();
+            let proof = ssal_commitment::get_inclusion_proof(
+                &params,
+                Curve::Bn254,
+                commitment_method,
+                &block,
+                BlockHeight::from(1),
+                TransactionOrder::from(size - 1),
+            )
+            .unwrap();
+            verify.bench_with_input(
+                BenchmarkId::new(commitment_method.to_string(), size),
+                &(&block[size - 1], proof),
+                |b, (raw_tx, proof)| {
+                    b.iter(|| {
+                        assert!(ssal_commitment::verify_inclusion_proof(
+                            &params,
+                            &block_commitment,
+                            raw_tx,
+                            proof,
+                        )
+                        .unwrap())
+                    })
+                },
+            );
+        }
+    }
+    verify.finish();
+
+    std::fs::remove_dir_all(&srs_dir).unwrap();
+}
+
+criterion_group!(benches, commitment);
+criterion_main!(benches);
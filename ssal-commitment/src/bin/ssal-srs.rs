@@ -0,0 +1,59 @@
+use std::{env, path::Path};
+
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::PairingEngine;
+use ssal_commitment::{
+    param::StructuredReferenceString,
+    srs::{checksum_path, CommitmentParam, CommitmentParamSet},
+    CHUNK_SIZE,
+};
+use ssal_core::{
+    error::{Error, WrapError},
+    rand, tracing, tracing_subscriber,
+    types::Curve,
+};
+
+/// Generate the SRS files of the given curves, or of every curve, into a directory for
+/// local testing. The trapdoor is drawn from the thread RNG and dropped, but nothing
+/// proves that to anyone else, so production deployments must use an SRS from a
+/// trusted setup.
+fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt().init();
+
+    let env_variables: Vec<String> = env::args().skip(1).collect();
+    let srs_dir = env_variables
+        .get(0)
+        .wrap("Provide the directory to write the SRS files to")?;
+    let curves = match env_variables.get(1..) {
+        Some(curves) if !curves.is_empty() => curves
+            .iter()
+            .map(|curve| curve.parse())
+            .collect::<Result<Vec<Curve>, Error>>()?,
+        _all => Curve::ALL.to_vec(),
+    };
+
+    for curve in curves {
+        let path = CommitmentParamSet::srs_path(srs_dir, curve);
+        let checksum = match curve {
+            Curve::Bn254 => generate::<Bn254>(&path)?,
+            Curve::Bls12_381 => generate::<Bls12_381>(&path)?,
+            Curve::Bls12_377 => generate::<Bls12_377>(&path)?,
+        };
+        tracing::info!(
+            "Wrote the {} SRS to {:?} (sha256: {}) and its checksum to {:?}",
+            curve,
+            path,
+            checksum,
+            checksum_path(&path),
+        );
+    }
+    Ok(())
+}
+
+fn generate<E: PairingEngine>(path: &Path) -> Result<String, Error> {
+    let srs =
+        StructuredReferenceString::<E, CHUNK_SIZE>::new_srs_for_testing(&mut rand::thread_rng());
+    Ok(CommitmentParam::save(&srs, path)?)
+}
@@ -0,0 +1,46 @@
+use std::{env, fs};
+
+use ssal_commitment::{evm, srs::CommitmentParamSet};
+use ssal_core::{
+    error::{Error, WrapError},
+    tracing, tracing_subscriber,
+    types::{CommitmentMethod, Curve},
+};
+
+/// Generate the Solidity contract verifying the inclusion proofs of a rollup on chain. The
+/// vector and KZG verifiers embed the parameters of the SRS in the directory, which must
+/// be the one the sequencers of the rollup use, and are checked against its SHA-256.
+fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt().init();
+
+    let env_variables: Vec<String> = env::args().skip(1).collect();
+    let srs_dir = env_variables
+        .get(0)
+        .wrap("Provide the directory of the SRS files")?;
+    let curve: Curve = env_variables
+        .get(1)
+        .wrap("Provide the curve of the rollup")?
+        .parse()?;
+    let commitment_method: CommitmentMethod = env_variables
+        .get(2)
+        .wrap("Provide the commitment method of the rollup")?
+        .parse()?;
+    let output = match env_variables.get(4) {
+        Some(output) => output.clone(),
+        None => format!("{}.sol", evm::VERIFIER_CONTRACT),
+    };
+
+    let params = match env_variables.get(3) {
+        Some(srs_checksum) => CommitmentParamSet::new(srs_dir).with_checksum(curve, srs_checksum),
+        None => CommitmentParamSet::new(srs_dir),
+    };
+    let source = evm::solidity_verifier(&params, curve, commitment_method)?;
+    fs::write(&output, source).wrap(format!("Failed to write {:?}", output))?;
+    tracing::info!(
+        "Wrote the verifier for {} on {} to {:?}",
+        commitment_method,
+        curve,
+        output,
+    );
+    Ok(())
+}
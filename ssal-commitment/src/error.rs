@@ -0,0 +1,117 @@
+use std::{io, path::PathBuf};
+
+use ark_serialize::SerializationError;
+use ssal_core::{
+    error::{Error, ErrorCode},
+    types::Curve,
+};
+
+/// The error of every fallible public API of ssal-commitment.
+#[derive(Debug)]
+pub enum CommitmentError {
+    /// More inputs than the parameters commit to.
+    TooManyInputs {
+        len: usize,
+        capacity: usize,
+    },
+    /// A position past the committed inputs.
+    PositionOutOfRange {
+        pos: usize,
+        len: usize,
+    },
+    /// The parameters are too short for polynomials of `degree`.
+    DegreeTooLarge {
+        degree: usize,
+        max_degree: usize,
+    },
+    /// The scalar field has no radix-2 evaluation domain of the size.
+    UnsupportedDomainSize(usize),
+    /// The EVM has no precompiles for pairings on the curve.
+    UnsupportedEvmCurve(Curve),
+    /// A proof carries a number of chunk commitments its commitment method does not allow.
+    InvalidChunkCount(usize),
+    /// Malformed encoding of a commitment, witness or SRS.
+    Serialization(SerializationError),
+    PolyCommit(ark_poly_commit::Error),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// No checksum is configured for the SRS of the curve.
+    MissingChecksum(Curve),
+    /// The SRS file does not match its configured checksum.
+    ChecksumMismatch {
+        path: PathBuf,
+        checksum: String,
+        expected: String,
+    },
+}
+
+impl std::fmt::Display for CommitmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooManyInputs { len, capacity } => write!(
+                f,
+                "{} inputs exceed the capacity of {} of the parameters",
+                len, capacity,
+            ),
+            Self::PositionOutOfRange { pos, len } => {
+                write!(f, "Position {} is out of range for {} inputs", pos, len)
+            }
+            Self::DegreeTooLarge { degree, max_degree } => write!(
+                f,
+                "Degree {} exceeds the maximum degree {} of the parameters",
+                degree, max_degree,
+            ),
+            Self::UnsupportedDomainSize(size) => {
+                write!(f, "No evaluation domain of size {}", size)
+            }
+            Self::UnsupportedEvmCurve(curve) => {
+                write!(f, "The EVM has no pairing precompiles for {}", curve)
+            }
+            Self::InvalidChunkCount(count) => write!(f, "Invalid number of chunks: {}", count),
+            Self::Serialization(error) => write!(f, "Serialization failed: {}", error),
+            Self::PolyCommit(error) => write!(f, "Polynomial commitment failed: {}", error),
+            Self::Io { path, source } => write!(f, "I/O on {:?} failed: {}", path, source),
+            Self::MissingChecksum(curve) => {
+                write!(f, "No checksum is configured for the SRS of {}", curve)
+            }
+            Self::ChecksumMismatch {
+                path,
+                checksum,
+                expected,
+            } => write!(
+                f,
+                "The checksum of the SRS file {:?} is {}, expected {}",
+                path, checksum, expected,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CommitmentError {}
+
+impl From<SerializationError> for CommitmentError {
+    fn from(value: SerializationError) -> Self {
+        Self::Serialization(value)
+    }
+}
+
+impl From<ark_poly_commit::Error> for CommitmentError {
+    fn from(value: ark_poly_commit::Error) -> Self {
+        Self::PolyCommit(value)
+    }
+}
+
+impl From<CommitmentError> for Error {
+    fn from(value: CommitmentError) -> Self {
+        let code = match &value {
+            CommitmentError::PositionOutOfRange { .. } => ErrorCode::NotFound,
+            CommitmentError::InvalidChunkCount(_) | CommitmentError::Serialization(_) => {
+                ErrorCode::InvalidRequest
+            }
+            _others => ErrorCode::Internal,
+        };
+        Error::boxed_error("Commitment", value).with_code(code)
+    }
+}
@@ -0,0 +1,106 @@
+/// The bn254 precompiles (EIP-196 and EIP-197) and encodings of ssal-commitment. G1 points
+/// are `[x, y]` and G2 points `[x.c1, x.c0, y.c1, y.c0]`, with the point at infinity as
+/// zeros.
+library Bn254 {
+    /// The modulus of the base field.
+    uint256 internal constant P = {{BASE_MODULUS}};
+    /// The modulus of the scalar field.
+    uint256 internal constant R = {{SCALAR_MODULUS}};
+    /// 2^256 mod R
+    uint256 internal constant R_2_256 = {{SCALAR_2_256}};
+
+    /// The input a transaction is committed as: `keccak256(0x00 || rawTx) ||
+    /// keccak256(0x01 || rawTx)` read as a big-endian integer and reduced modulo R.
+    function hashToField(bytes calldata rawTx) internal pure returns (uint256) {
+        uint256 high = uint256(keccak256(abi.encodePacked(uint8(0x00), rawTx)));
+        uint256 low = uint256(keccak256(abi.encodePacked(uint8(0x01), rawTx)));
+        return addmod(mulmod(high, R_2_256, R), low, R);
+    }
+
+    /// keccak256 of the compressed chunk commitments, which is the block commitment.
+    function aggregate(uint256[2][] memory points) internal pure returns (bytes32) {
+        bytes memory encoded = new bytes(32 * points.length);
+        for (uint256 i = 0; i < points.length; i++) {
+            uint256 word = compress(points[i]);
+            assembly {
+                mstore(add(encoded, mul(add(i, 1), 32)), word)
+            }
+        }
+        return keccak256(encoded);
+    }
+
+    /// The compressed encoding of arkworks: x in little-endian with the top bit of the
+    /// last byte set when y > -y, or only the infinity bit for the point at infinity.
+    function compress(uint256[2] memory point) internal pure returns (uint256) {
+        if (point[0] == 0 && point[1] == 0) {
+            return 0x40;
+        }
+        uint256 word = reverseBytes(point[0]);
+        if (point[1] > (P - 1) / 2) {
+            word |= 0x80;
+        }
+        return word;
+    }
+
+    function reverseBytes(uint256 value) internal pure returns (uint256) {
+        value = ((value & 0xFF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00) >> 8) |
+            ((value & 0x00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF) << 8);
+        value = ((value & 0xFFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000) >> 16) |
+            ((value & 0x0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF) << 16);
+        value = ((value & 0xFFFFFFFF00000000FFFFFFFF00000000FFFFFFFF00000000FFFFFFFF00000000) >> 32) |
+            ((value & 0x00000000FFFFFFFF00000000FFFFFFFF00000000FFFFFFFF00000000FFFFFFFF) << 32);
+        value = ((value & 0xFFFFFFFFFFFFFFFF0000000000000000FFFFFFFFFFFFFFFF0000000000000000) >> 64) |
+            ((value & 0x0000000000000000FFFFFFFFFFFFFFFF0000000000000000FFFFFFFFFFFFFFFF) << 64);
+        return (value >> 128) | (value << 128);
+    }
+
+    function negate(uint256[2] memory point) internal pure returns (uint256[2] memory) {
+        if (point[0] == 0 && point[1] == 0) {
+            return point;
+        }
+        return [point[0], (P - (point[1] % P)) % P];
+    }
+
+    function add(uint256[2] memory a, uint256[2] memory b)
+        internal
+        view
+        returns (bool success, uint256[2] memory sum)
+    {
+        uint256[4] memory input = [a[0], a[1], b[0], b[1]];
+        assembly {
+            success := staticcall(gas(), 0x06, input, 128, sum, 64)
+        }
+    }
+
+    function mul(uint256[2] memory point, uint256 scalar)
+        internal
+        view
+        returns (bool success, uint256[2] memory product)
+    {
+        uint256[3] memory input = [point[0], point[1], scalar];
+        assembly {
+            success := staticcall(gas(), 0x07, input, 96, product, 64)
+        }
+    }
+
+    /// Whether the product of the pairings of the `(G1, G2)` pairs in `input` is one.
+    function pairing(uint256[] memory input) internal view returns (bool) {
+        uint256[1] memory result;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x08, add(input, 32), mul(mload(input), 32), result, 32)
+        }
+        return success && result[0] == 1;
+    }
+
+    /// The G2 point at `index` of consecutive 128-byte points.
+    function g2At(bytes memory points, uint256 index) internal pure returns (uint256[4] memory point) {
+        assembly {
+            let offset := add(add(points, 32), mul(index, 128))
+            mstore(point, mload(offset))
+            mstore(add(point, 32), mload(add(offset, 32)))
+            mstore(add(point, 64), mload(add(offset, 64)))
+            mstore(add(point, 96), mload(add(offset, 96)))
+        }
+    }
+}
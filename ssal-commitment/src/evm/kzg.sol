@@ -0,0 +1,109 @@
+/// Verifies the inclusion of transactions in the blocks of a rollup committed to with the
+/// KZG commitment on bn254, for the SRS the contract was generated from.
+contract InclusionVerifier {
+    uint8 public constant CURVE = {{CURVE}};
+    uint8 public constant COMMITMENT_METHOD = {{COMMITMENT_METHOD}};
+    uint256 public constant CHUNK_SIZE = {{CHUNK_SIZE}};
+
+    /// The generator of the evaluation domain, at whose powers the inputs are opened.
+    uint256 internal constant OMEGA = {{OMEGA}};
+    /// The generator G of G1.
+    uint256 internal constant G_X = {{G_X}};
+    uint256 internal constant G_Y = {{G_Y}};
+    /// The generator H of G2.
+    uint256 internal constant H_X1 = {{H_X1}};
+    uint256 internal constant H_X0 = {{H_X0}};
+    uint256 internal constant H_Y1 = {{H_Y1}};
+    uint256 internal constant H_Y0 = {{H_Y0}};
+    /// beta * H
+    uint256 internal constant BETA_H_X1 = {{BETA_H_X1}};
+    uint256 internal constant BETA_H_X0 = {{BETA_H_X0}};
+    uint256 internal constant BETA_H_Y1 = {{BETA_H_Y1}};
+    uint256 internal constant BETA_H_Y0 = {{BETA_H_Y0}};
+
+    /// Whether `rawTx` is the transaction `txOrder` of the block committed to by
+    /// `blockCommitment`, the tagged bytes sent with the block. `proof` is the ABI-encoded
+    /// `(uint256[2][] chunkCommitments, uint256[2] witness)` of the inclusion proof.
+    function verifyInclusion(
+        bytes calldata blockCommitment,
+        bytes calldata rawTx,
+        uint256 txOrder,
+        bytes calldata proof
+    ) external view returns (bool) {
+        if (
+            blockCommitment.length != 34 ||
+            uint8(blockCommitment[0]) != CURVE ||
+            uint8(blockCommitment[1]) != COMMITMENT_METHOD
+        ) {
+            return false;
+        }
+        (uint256[2][] memory chunkCommitments, uint256[2] memory witness) =
+            abi.decode(proof, (uint256[2][], uint256[2]));
+        uint256 chunk = txOrder / CHUNK_SIZE;
+        if (
+            chunk >= chunkCommitments.length ||
+            Bn254.aggregate(chunkCommitments) != bytes32(blockCommitment[2:34])
+        ) {
+            return false;
+        }
+        return verifyOpening(
+            chunkCommitments[chunk],
+            Bn254.hashToField(rawTx),
+            txOrder % CHUNK_SIZE,
+            witness
+        );
+    }
+
+    /// e(C - input * G + z * W, H) * e(-W, beta * H) == 1 with z = OMEGA^pos, which is
+    /// e(C - input * G, H) == e(W, beta * H - z * H) rearranged for the precompile.
+    function verifyOpening(
+        uint256[2] memory commitment,
+        uint256 input,
+        uint256 pos,
+        uint256[2] memory witness
+    ) internal view returns (bool) {
+        (bool success, uint256[2] memory point) = Bn254.mul([G_X, G_Y], Bn254.R - input);
+        if (!success) {
+            return false;
+        }
+        (success, point) = Bn254.add(commitment, point);
+        if (!success) {
+            return false;
+        }
+        uint256[2] memory scaledWitness;
+        (success, scaledWitness) = Bn254.mul(witness, pow(OMEGA, pos));
+        if (!success) {
+            return false;
+        }
+        (success, point) = Bn254.add(point, scaledWitness);
+        if (!success) {
+            return false;
+        }
+        uint256[2] memory negWitness = Bn254.negate(witness);
+
+        uint256[] memory pairs = new uint256[](12);
+        pairs[0] = point[0];
+        pairs[1] = point[1];
+        pairs[2] = H_X1;
+        pairs[3] = H_X0;
+        pairs[4] = H_Y1;
+        pairs[5] = H_Y0;
+        pairs[6] = negWitness[0];
+        pairs[7] = negWitness[1];
+        pairs[8] = BETA_H_X1;
+        pairs[9] = BETA_H_X0;
+        pairs[10] = BETA_H_Y1;
+        pairs[11] = BETA_H_Y0;
+        return Bn254.pairing(pairs);
+    }
+
+    function pow(uint256 base, uint256 exponent) internal pure returns (uint256 result) {
+        result = 1;
+        for (; exponent > 0; exponent >>= 1) {
+            if (exponent & 1 == 1) {
+                result = mulmod(result, base, Bn254.R);
+            }
+            base = mulmod(base, base, Bn254.R);
+        }
+    }
+}
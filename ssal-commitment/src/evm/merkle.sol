@@ -0,0 +1,43 @@
+/// Verifies the inclusion of transactions in the blocks of a rollup committed to with the
+/// {{HASH}} Merkle tree.
+contract InclusionVerifier {
+    uint8 public constant CURVE = {{CURVE}};
+    uint8 public constant COMMITMENT_METHOD = {{COMMITMENT_METHOD}};
+
+    /// Whether `rawTx` is the transaction `txOrder` of the block committed to by
+    /// `blockCommitment`, the tagged bytes sent with the block. `proof` is the ABI-encoded
+    /// `bytes32[]` of the siblings from the leaf up.
+    function verifyInclusion(
+        bytes calldata blockCommitment,
+        bytes calldata rawTx,
+        uint256 txOrder,
+        bytes calldata proof
+    ) external pure returns (bool) {
+        if (
+            blockCommitment.length != 34 ||
+            uint8(blockCommitment[0]) != CURVE ||
+            uint8(blockCommitment[1]) != COMMITMENT_METHOD
+        ) {
+            return false;
+        }
+        bytes32[] memory siblings = abi.decode(proof, (bytes32[]));
+        if (txOrder >> siblings.length != 0) {
+            return false;
+        }
+
+        // leaf = H(0x00 || input), node = H(0x01 || left || right)
+        bytes32 node = hash(abi.encodePacked(uint8(0x00), hash(rawTx)));
+        for (uint256 height = 0; height < siblings.length; height++) {
+            if ((txOrder >> height) & 1 == 0) {
+                node = hash(abi.encodePacked(uint8(0x01), node, siblings[height]));
+            } else {
+                node = hash(abi.encodePacked(uint8(0x01), siblings[height], node));
+            }
+        }
+        return node == bytes32(blockCommitment[2:34]);
+    }
+
+    function hash(bytes memory data) internal pure returns (bytes32) {
+        return {{HASH}}(data);
+    }
+}
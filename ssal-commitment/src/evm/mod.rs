@@ -0,0 +1,259 @@
+//! The EVM encoding of inclusion proofs and the Solidity contract verifying them.
+//!
+//! Every generated contract is named [`VERIFIER_CONTRACT`] and exposes
+//! `verifyInclusion(bytes blockCommitment, bytes rawTx, uint256 txOrder, bytes proof)`,
+//! where `blockCommitment` is [`BlockCommitment::to_bytes`], as sent to the chain with
+//! the block. The EVM only has pairing precompiles for bn254 (EIP-196 and EIP-197), so the
+//! vector and KZG commitments are verifiable on chain on bn254 only. The Merkle
+//! commitments do not depend on the curve.
+
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{BigInteger, Field, FpParameters, PrimeField, Zero};
+use ssal_core::{
+    ethers_core::{
+        abi::{self, Token},
+        types::{Bytes, U256},
+        utils::{hex, keccak256},
+    },
+    types::*,
+};
+
+use crate::{
+    error::CommitmentError,
+    merkle::{Keccak256Hasher, MerkleCommitment, Sha256Hasher},
+    srs::{CommitmentParam, CommitmentParamSet},
+    vc::deserialize_point,
+    CommitmentScheme, CHUNK_SIZE,
+};
+
+const BN254: &str = include_str!("bn254.sol");
+const VECTOR_COMMITMENT: &str = include_str!("vector_commitment.sol");
+const KZG: &str = include_str!("kzg.sol");
+const MERKLE: &str = include_str!("merkle.sol");
+
+/// The name of the contract generated by [`solidity_verifier`].
+pub const VERIFIER_CONTRACT: &str = "InclusionVerifier";
+
+/// The signature of the verification function of [`VERIFIER_CONTRACT`].
+pub const VERIFY_INCLUSION: &str = "verifyInclusion(bytes,bytes,uint256,bytes)";
+
+/// The ABI-encoded `proof` argument of `verifyInclusion`:
+/// - `(uint256[2][] chunkCommitments, uint256[2] witness)` with uncompressed points for
+///   the vector and KZG commitments.
+/// - `bytes32[] siblings` for the Merkle commitments.
+pub fn encode_inclusion_proof(
+    block_commitment: &BlockCommitment,
+    proof: &InclusionProof,
+) -> Result<Bytes, CommitmentError> {
+    let tokens = match block_commitment.commitment_method() {
+        CommitmentMethod::MerkleKeccak256 => merkle_tokens(
+            MerkleCommitment::<Keccak256Hasher>::witness_from_bytes(proof.witness())?,
+        ),
+        CommitmentMethod::MerkleSha256 => merkle_tokens(
+            MerkleCommitment::<Sha256Hasher>::witness_from_bytes(proof.witness())?,
+        ),
+        CommitmentMethod::VectorCommitment | CommitmentMethod::Kzg => {
+            check_curve(block_commitment.curve())?;
+            let chunk_commitments = proof
+                .chunk_commitments()
+                .iter()
+                .map(|chunk_commitment| g1_token(chunk_commitment))
+                .collect::<Result<Vec<Token>, CommitmentError>>()?;
+            vec![Token::Array(chunk_commitments), g1_token(proof.witness())?]
+        }
+    };
+    Ok(abi::encode(&tokens).into())
+}
+
+/// The calldata of `verifyInclusion` for `raw_tx` and its inclusion proof.
+pub fn encode_verify_inclusion(
+    block_commitment: &BlockCommitment,
+    raw_tx: &RawTransaction,
+    proof: &InclusionProof,
+) -> Result<Bytes, CommitmentError> {
+    let arguments = abi::encode(&[
+        Token::Bytes(block_commitment.to_bytes()),
+        Token::Bytes(raw_tx.as_ref().to_vec()),
+        Token::Uint(U256::from(proof.tx_order().value())),
+        Token::Bytes(encode_inclusion_proof(block_commitment, proof)?.to_vec()),
+    ]);
+
+    let mut calldata = keccak256(VERIFY_INCLUSION)[..4].to_vec();
+    calldata.extend_from_slice(&arguments);
+    Ok(calldata.into())
+}
+
+/// The Solidity source of [`VERIFIER_CONTRACT`] for the blocks of rollups on `curve`
+/// committed to with `commitment_method`. The vector and KZG verifiers embed the
+/// parameters of the SRS in `params`, so they only accept the commitments made with it.
+pub fn solidity_verifier(
+    params: &CommitmentParamSet,
+    curve: Curve,
+    commitment_method: CommitmentMethod,
+) -> Result<String, CommitmentError> {
+    let contract = match commitment_method {
+        CommitmentMethod::MerkleKeccak256 => render(MERKLE, &[("HASH", "keccak256".into())]),
+        CommitmentMethod::MerkleSha256 => render(MERKLE, &[("HASH", "sha256".into())]),
+        CommitmentMethod::VectorCommitment => {
+            check_curve(curve)?;
+            let library = bn254_library();
+            let param = params.bn254()?;
+            let contract = render(VECTOR_COMMITMENT, &vector_commitment_values(param));
+            format!("{}\n{}", library, contract)
+        }
+        CommitmentMethod::Kzg => {
+            check_curve(curve)?;
+            let library = bn254_library();
+            let param = params.bn254()?;
+            let contract = render(KZG, &kzg_values(param));
+            format!("{}\n{}", library, contract)
+        }
+    };
+    let contract = render(
+        &contract,
+        &[
+            ("CURVE", curve.tag().to_string()),
+            ("COMMITMENT_METHOD", commitment_method.tag().to_string()),
+            ("CHUNK_SIZE", CHUNK_SIZE.to_string()),
+        ],
+    );
+
+    Ok(format!(
+        "// SPDX-License-Identifier: MIT\n\
+         // Generated by ssal-commitment for {} on {}. Do not edit.\n\
+         pragma solidity ^0.8.19;\n\n{}",
+        commitment_method, curve, contract,
+    ))
+}
+
+fn check_curve(curve: Curve) -> Result<(), CommitmentError> {
+    match curve {
+        Curve::Bn254 => Ok(()),
+        curve => Err(CommitmentError::UnsupportedEvmCurve(curve)),
+    }
+}
+
+/// Replace every `{{NAME}}` of `template` with its value.
+fn render(template: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_owned(), |source, (name, value)| {
+            source.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+fn bn254_library() -> String {
+    let two_256 = Fr::from(2u64).pow([256]);
+    render(
+        BN254,
+        &[
+            ("BASE_MODULUS", modulus::<Fq>().to_string()),
+            ("SCALAR_MODULUS", modulus::<Fr>().to_string()),
+            ("SCALAR_2_256", to_u256(&two_256).to_string()),
+        ],
+    )
+}
+
+fn vector_commitment_values(param: &CommitmentParam<Bn254>) -> Vec<(&'static str, String)> {
+    let neg_g0 = -param.prover_param().g[0];
+    let [neg_g0_x, neg_g0_y] = g1_words(&neg_g0);
+    let powers_of_h: Vec<u8> = param
+        .verifier_param()
+        .h
+        .iter()
+        .flat_map(|h| g2_words(h).into_iter().flat_map(u256_bytes))
+        .collect();
+
+    let mut values = vec![
+        ("NEG_G0_X", neg_g0_x.to_string()),
+        ("NEG_G0_Y", neg_g0_y.to_string()),
+        ("POWERS_OF_H", hex::encode(powers_of_h)),
+    ];
+    values.extend(g2_values(
+        ["H_X1", "H_X0", "H_Y1", "H_Y0"],
+        &G2Affine::prime_subgroup_generator(),
+    ));
+    values
+}
+
+fn kzg_values(param: &CommitmentParam<Bn254>) -> Vec<(&'static str, String)> {
+    let verifier_param = param.kzg_verifier_param();
+    let verifier_key = verifier_param.verifier_key();
+    let [g_x, g_y] = g1_words(&verifier_key.g);
+
+    let mut values = vec![
+        (
+            "OMEGA",
+            to_u256(&verifier_param.domain().group_gen).to_string(),
+        ),
+        ("G_X", g_x.to_string()),
+        ("G_Y", g_y.to_string()),
+    ];
+    values.extend(g2_values(["H_X1", "H_X0", "H_Y1", "H_Y0"], &verifier_key.h));
+    values.extend(g2_values(
+        ["BETA_H_X1", "BETA_H_X0", "BETA_H_Y1", "BETA_H_Y0"],
+        &verifier_key.beta_h,
+    ));
+    values
+}
+
+fn g2_values(names: [&'static str; 4], point: &G2Affine) -> Vec<(&'static str, String)> {
+    names
+        .into_iter()
+        .zip(g2_words(point))
+        .map(|(name, word)| (name, word.to_string()))
+        .collect()
+}
+
+fn merkle_tokens(siblings: Vec<[u8; 32]>) -> Vec<Token> {
+    vec![Token::Array(
+        siblings
+            .into_iter()
+            .map(|sibling| Token::FixedBytes(sibling.to_vec()))
+            .collect(),
+    )]
+}
+
+/// `uint256[2]` of the compressed point in `bytes`.
+fn g1_token(bytes: &[u8]) -> Result<Token, CommitmentError> {
+    let point = deserialize_point::<Bn254>(bytes)?.into_affine();
+    Ok(Token::FixedArray(
+        g1_words(&point).into_iter().map(Token::Uint).collect(),
+    ))
+}
+
+/// `[x, y]`, or zeros for the point at infinity.
+fn g1_words(point: &G1Affine) -> [U256; 2] {
+    if point.is_zero() {
+        return [U256::zero(); 2];
+    }
+    [to_u256(&point.x), to_u256(&point.y)]
+}
+
+/// `[x.c1, x.c0, y.c1, y.c0]`, or zeros for the point at infinity.
+fn g2_words(point: &G2Affine) -> [U256; 4] {
+    if point.is_zero() {
+        return [U256::zero(); 4];
+    }
+    [
+        to_u256(&point.x.c1),
+        to_u256(&point.x.c0),
+        to_u256(&point.y.c1),
+        to_u256(&point.y.c0),
+    ]
+}
+
+fn to_u256<F: PrimeField>(value: &F) -> U256 {
+    U256::from_big_endian(&value.into_repr().to_bytes_be())
+}
+
+fn modulus<F: PrimeField>() -> U256 {
+    U256::from_big_endian(&F::Params::MODULUS.to_bytes_be())
+}
+
+fn u256_bytes(word: U256) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    word.to_big_endian(&mut bytes);
+    bytes
+}
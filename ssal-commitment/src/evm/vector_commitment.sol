@@ -0,0 +1,90 @@
+/// Verifies the inclusion of transactions in the blocks of a rollup committed to with the
+/// vector commitment on bn254, for the SRS the contract was generated from.
+contract InclusionVerifier {
+    uint8 public constant CURVE = {{CURVE}};
+    uint8 public constant COMMITMENT_METHOD = {{COMMITMENT_METHOD}};
+    uint256 public constant CHUNK_SIZE = {{CHUNK_SIZE}};
+
+    /// -g[0] = -(alpha * G)
+    uint256 internal constant NEG_G0_X = {{NEG_G0_X}};
+    uint256 internal constant NEG_G0_Y = {{NEG_G0_Y}};
+    /// The generator H of G2.
+    uint256 internal constant H_X1 = {{H_X1}};
+    uint256 internal constant H_X0 = {{H_X0}};
+    uint256 internal constant H_Y1 = {{H_Y1}};
+    uint256 internal constant H_Y0 = {{H_Y0}};
+    /// h[i] = alpha^(i + 1) * H for i in 0..CHUNK_SIZE.
+    bytes internal constant POWERS_OF_H = hex"{{POWERS_OF_H}}";
+
+    /// Whether `rawTx` is the transaction `txOrder` of the block committed to by
+    /// `blockCommitment`, the tagged bytes sent with the block. `proof` is the ABI-encoded
+    /// `(uint256[2][] chunkCommitments, uint256[2] witness)` of the inclusion proof.
+    function verifyInclusion(
+        bytes calldata blockCommitment,
+        bytes calldata rawTx,
+        uint256 txOrder,
+        bytes calldata proof
+    ) external view returns (bool) {
+        if (
+            blockCommitment.length != 34 ||
+            uint8(blockCommitment[0]) != CURVE ||
+            uint8(blockCommitment[1]) != COMMITMENT_METHOD
+        ) {
+            return false;
+        }
+        (uint256[2][] memory chunkCommitments, uint256[2] memory witness) =
+            abi.decode(proof, (uint256[2][], uint256[2]));
+        uint256 chunk = txOrder / CHUNK_SIZE;
+        if (
+            chunk >= chunkCommitments.length ||
+            Bn254.aggregate(chunkCommitments) != bytes32(blockCommitment[2:34])
+        ) {
+            return false;
+        }
+        return verifyOpening(
+            chunkCommitments[chunk],
+            Bn254.hashToField(rawTx),
+            txOrder % CHUNK_SIZE,
+            witness
+        );
+    }
+
+    /// e(C, h[N - pos - 1]) * e(-W, H) * e(-input * g[0], h[N - 1]) == 1, which is the
+    /// check of ssal-commitment with t = e(g[0], h[N - 1]).
+    function verifyOpening(
+        uint256[2] memory commitment,
+        uint256 input,
+        uint256 pos,
+        uint256[2] memory witness
+    ) internal view returns (bool) {
+        (bool success, uint256[2] memory scaled) = Bn254.mul([NEG_G0_X, NEG_G0_Y], input);
+        if (!success) {
+            return false;
+        }
+        bytes memory powersOfH = POWERS_OF_H;
+        uint256[4] memory hPos = Bn254.g2At(powersOfH, CHUNK_SIZE - pos - 1);
+        uint256[4] memory hLast = Bn254.g2At(powersOfH, CHUNK_SIZE - 1);
+        uint256[2] memory negWitness = Bn254.negate(witness);
+
+        uint256[] memory pairs = new uint256[](18);
+        pairs[0] = commitment[0];
+        pairs[1] = commitment[1];
+        pairs[2] = hPos[0];
+        pairs[3] = hPos[1];
+        pairs[4] = hPos[2];
+        pairs[5] = hPos[3];
+        pairs[6] = negWitness[0];
+        pairs[7] = negWitness[1];
+        pairs[8] = H_X1;
+        pairs[9] = H_X0;
+        pairs[10] = H_Y1;
+        pairs[11] = H_Y0;
+        pairs[12] = scaled[0];
+        pairs[13] = scaled[1];
+        pairs[14] = hLast[0];
+        pairs[15] = hLast[1];
+        pairs[16] = hLast[2];
+        pairs[17] = hLast[3];
+        return Bn254.pairing(pairs);
+    }
+}
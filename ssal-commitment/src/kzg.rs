@@ -1,24 +1,232 @@
 use std::ops::Div;
 
-use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
-use ark_ff::PrimeField;
-use ark_poly::UVPolynomial;
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain,
+    UVPolynomial,
+};
 use ark_poly_commit::{
-    kzg10::{Powers, Proof, Randomness, UniversalParams, VerifierKey, KZG10},
-    PCUniversalParams,
+    kzg10::{self, Powers, Proof, Randomness, UniversalParams, VerifierKey, KZG10},
+    PCRandomness, PCUniversalParams,
+};
+use ark_std::{borrow::Cow, end_timer, rand::RngCore, start_timer};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    error::CommitmentError,
+    hash_to_field,
+    vc::{deserialize_point, serialize_point},
+    CommitmentScheme, Opening,
 };
-use ark_std::{borrow::Cow, end_timer, start_timer};
 
-pub fn trim<E>(pp: &UniversalParams<E>, mut supported_degree: usize) -> (Powers<E>, VerifierKey<E>)
+/// A KZG commitment to the polynomial `p` of degree below the domain size with
+/// `p(omega^i) = inputs[i]`, where `omega` generates the radix-2 evaluation domain and
+/// the evaluations past the inputs are zero. An input is opened by evaluating `p` at
+/// the domain element of its position.
+pub struct KzgCommitment<E: PairingEngine> {
+    commitment: kzg10::Commitment<E>,
+}
+
+pub struct KzgProverParam<E: PairingEngine> {
+    powers: Powers<'static, E>,
+    domain: Radix2EvaluationDomain<E::Fr>,
+}
+
+pub struct KzgVerifierParam<E: PairingEngine> {
+    verifier_key: VerifierKey<E>,
+    domain: Radix2EvaluationDomain<E::Fr>,
+}
+
+/// Trim `pp` to commit to `domain_size` inputs, rounded up to a power of two.
+pub fn setup<E>(
+    pp: &UniversalParams<E>,
+    domain_size: usize,
+) -> Result<(KzgProverParam<E>, KzgVerifierParam<E>), CommitmentError>
 where
     E: PairingEngine,
 {
-    // Needs explanation.
-    if supported_degree == 1 {
-        supported_degree += 1;
+    let domain = Radix2EvaluationDomain::<E::Fr>::new(domain_size)
+        .ok_or(CommitmentError::UnsupportedDomainSize(domain_size))?;
+    let (powers, verifier_key) = trim(pp, domain.size() - 1)?;
+    let powers = Powers {
+        powers_of_g: Cow::Owned(powers.powers_of_g.into_owned()),
+        powers_of_gamma_g: Cow::Owned(powers.powers_of_gamma_g.into_owned()),
+    };
+    Ok((
+        KzgProverParam { powers, domain },
+        KzgVerifierParam {
+            verifier_key,
+            domain,
+        },
+    ))
+}
+
+impl<E: PairingEngine> KzgVerifierParam<E> {
+    pub(crate) fn verifier_key(&self) -> &VerifierKey<E> {
+        &self.verifier_key
+    }
+
+    pub(crate) fn domain(&self) -> &Radix2EvaluationDomain<E::Fr> {
+        &self.domain
+    }
+}
+
+fn interpolate<E: PairingEngine>(
+    domain: Radix2EvaluationDomain<E::Fr>,
+    inputs: &[E::Fr],
+) -> DensePolynomial<E::Fr> {
+    let mut evaluations = inputs.to_vec();
+    evaluations.resize(domain.size(), E::Fr::zero());
+    Evaluations::from_vec_and_domain(evaluations, domain).interpolate()
+}
+
+impl<E: PairingEngine> CommitmentScheme for KzgCommitment<E> {
+    type ProverParam = KzgProverParam<E>;
+    type VerifierParam = KzgVerifierParam<E>;
+    type MessageUnit = E::Fr;
+    type Commitment = Self;
+    type Witness = Proof<E>;
+
+    fn hash_to_message_unit(bytes: &[u8]) -> Self::MessageUnit {
+        hash_to_field(bytes)
+    }
+
+    fn commit(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, CommitmentError> {
+        check_capacity(pp, inputs)?;
+
+        let polynomial = interpolate::<E>(pp.domain, inputs);
+        let (commitment, _randomness) =
+            KZG10::<E, DensePolynomial<E::Fr>>::commit(&pp.powers, &polynomial, None, None)?;
+        Ok(Self { commitment })
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, CommitmentError> {
+        check_capacity(pp, inputs)?;
+        if pos >= inputs.len() {
+            return Err(CommitmentError::PositionOutOfRange {
+                pos,
+                len: inputs.len(),
+            });
+        }
+
+        let polynomial = interpolate::<E>(pp.domain, inputs);
+        open(
+            &pp.powers,
+            &polynomial,
+            pp.domain.element(pos),
+            &Randomness::empty(),
+        )
+    }
+
+    fn verify(
+        &self,
+        vp: &Self::VerifierParam,
+        input: &Self::MessageUnit,
+        pos: usize,
+        witness: &Self::Witness,
+    ) -> bool {
+        if pos >= vp.domain.size() {
+            return false;
+        }
+        KZG10::<E, DensePolynomial<E::Fr>>::check(
+            &vp.verifier_key,
+            &self.commitment,
+            vp.domain.element(pos),
+            *input,
+            witness,
+        )
+        .unwrap_or(false)
+    }
+
+    fn batch_verify<R: RngCore>(
+        &self,
+        vp: &Self::VerifierParam,
+        openings: &[Opening<Self>],
+        rng: &mut R,
+    ) -> bool {
+        if openings.iter().any(|(_, pos, _)| *pos >= vp.domain.size()) {
+            return false;
+        }
+
+        let commitments = vec![self.commitment; openings.len()];
+        let points: Vec<E::Fr> = openings
+            .iter()
+            .map(|(_, pos, _)| vp.domain.element(*pos))
+            .collect();
+        let values: Vec<E::Fr> = openings.iter().map(|(input, _, _)| *input).collect();
+        let proofs: Vec<Proof<E>> = openings.iter().map(|(_, _, witness)| *witness).collect();
+        KZG10::<E, DensePolynomial<E::Fr>>::batch_check(
+            &vp.verifier_key,
+            &commitments,
+            &points,
+            &values,
+            &proofs,
+            rng,
+        )
+        .unwrap_or(false)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        serialize_point::<E>(&self.commitment.0.into_projective())
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, CommitmentError> {
+        Ok(Self {
+            commitment: kzg10::Commitment(deserialize_point::<E>(bytes)?.into_affine()),
+        })
+    }
+
+    /// Only the witness point is encoded since commitments are never hiding.
+    fn witness_to_bytes(witness: &Self::Witness) -> Vec<u8> {
+        serialize_point::<E>(&witness.w.into_projective())
+    }
+
+    fn witness_from_bytes(bytes: &[u8]) -> Result<Self::Witness, CommitmentError> {
+        Ok(Proof {
+            w: deserialize_point::<E>(bytes)?.into_affine(),
+            random_v: None,
+        })
+    }
+}
+
+fn check_capacity<E: PairingEngine>(
+    pp: &KzgProverParam<E>,
+    inputs: &[E::Fr],
+) -> Result<(), CommitmentError> {
+    if inputs.len() > pp.domain.size() {
+        return Err(CommitmentError::TooManyInputs {
+            len: inputs.len(),
+            capacity: pp.domain.size(),
+        });
+    }
+    Ok(())
+}
+
+/// The powers of `pp` for polynomials of degree up to `supported_degree`. Commitments
+/// are never hiding, so no extra powers of `gamma * G` are kept for the blinding
+/// polynomial.
+pub fn trim<E>(
+    pp: &UniversalParams<E>,
+    supported_degree: usize,
+) -> Result<(Powers<E>, VerifierKey<E>), CommitmentError>
+where
+    E: PairingEngine,
+{
+    if supported_degree > pp.max_degree() {
+        return Err(CommitmentError::DegreeTooLarge {
+            degree: supported_degree,
+            max_degree: pp.max_degree(),
+        });
     }
-    // Check length. All assertions must be turned into Result<T, E> for the production.
-    assert!(supported_degree <= pp.max_degree());
 
     let powers_of_gamma_g = pp
         .powers_of_gamma_g
@@ -41,12 +249,20 @@ where
         prepared_beta_h: pp.prepared_beta_h.to_owned(),
     };
 
-    (powers, vk)
+    Ok((powers, vk))
 }
 
-pub(crate) fn check_degree_is_too_large(degree: usize, num_powers: usize) -> bool {
-    let num_coefficients = degree + 1;
-    num_coefficients <= num_powers
+fn check_degree<E: PairingEngine>(
+    degree: usize,
+    powers: &Powers<E>,
+) -> Result<(), CommitmentError> {
+    if degree + 1 > powers.size() {
+        return Err(CommitmentError::DegreeTooLarge {
+            degree,
+            max_degree: powers.size().saturating_sub(1),
+        });
+    }
+    Ok(())
 }
 
 fn open_with_witness_polynomial<E, P>(
@@ -55,16 +271,13 @@ fn open_with_witness_polynomial<E, P>(
     randomness: &Randomness<E::Fr, P>,
     witness_polynomial: &P,
     hiding_witness_polynomial: Option<&P>,
-) -> Proof<E>
+) -> Result<Proof<E>, CommitmentError>
 where
     E: PairingEngine,
     P: UVPolynomial<E::Fr, Point = E::Fr>,
     for<'a, 'b> &'a P: Div<&'b P, Output = P>,
 {
-    assert!(
-        check_degree_is_too_large(witness_polynomial.degree(), powers.size()),
-        "degree is too large"
-    );
+    check_degree(witness_polynomial.degree(), powers)?;
     let (num_leading_zeros, witness_coeffs) =
         skip_leading_zeros_and_convert_to_bigints(witness_polynomial);
 
@@ -81,7 +294,7 @@ where
         let blinding_evaluation = blinding_p.evaluate(&point);
         end_timer!(blinding_eval_time);
 
-        let random_witness_coeffs = convert_to_bigints(&hiding_witness_polynomial.coeffs());
+        let random_witness_coeffs = convert_to_bigints(hiding_witness_polynomial.coeffs());
         let witness_comm_time =
             start_timer!(|| "Computing commitment to random witness polynomial");
         w += &VariableBaseMSM::multi_scalar_mul(&powers.powers_of_gamma_g, &random_witness_coeffs);
@@ -91,10 +304,10 @@ where
         None
     };
 
-    Proof {
+    Ok(Proof {
         w: w.into_affine(),
         random_v,
-    }
+    })
 }
 
 /// On input a polynomial `p` and a point `point`, outputs a proof for the same.
@@ -103,23 +316,19 @@ pub fn open<E, P>(
     p: &P,
     point: P::Point,
     rand: &Randomness<E::Fr, P>,
-) -> Proof<E>
+) -> Result<Proof<E>, CommitmentError>
 where
     E: PairingEngine,
     P: UVPolynomial<E::Fr, Point = E::Fr>,
     for<'a, 'b> &'a P: Div<&'b P, Output = P>,
 {
-    // Assertion will be removed in the production and `Result<T, E>` will be returned.
-    assert!(
-        check_degree_is_too_large(p.degree(), powers.size()),
-        "degree is too large"
-    );
+    check_degree(p.degree(), powers)?;
 
     let open_time = start_timer!(|| format!("Opening polynomial of degree {}", p.degree()));
 
     let witness_time = start_timer!(|| "Computing witness polynomials");
     let (witness_poly, hiding_witness_poly) =
-        KZG10::<E, P>::compute_witness_polynomial(p, point, rand).unwrap();
+        KZG10::<E, P>::compute_witness_polynomial(p, point, rand)?;
     end_timer!(witness_time);
 
     let proof = open_with_witness_polynomial(
@@ -128,17 +337,20 @@ where
         rand,
         &witness_poly,
         hiding_witness_poly.as_ref(),
-    );
+    )?;
 
     end_timer!(open_time);
-    proof
+    Ok(proof)
 }
 
 fn skip_leading_zeros_and_convert_to_bigints<F: PrimeField, P: UVPolynomial<F>>(
     p: &P,
 ) -> (usize, Vec<F::BigInt>) {
-    // Less verbose for the same performance.
-    let num_leading_zeros = p.coeffs().iter().filter(|coeff| coeff.is_zero()).count();
+    let num_leading_zeros = p
+        .coeffs()
+        .iter()
+        .take_while(|coeff| coeff.is_zero())
+        .count();
     let coeffs = convert_to_bigints(&p.coeffs()[num_leading_zeros..]);
     (num_leading_zeros, coeffs)
 }
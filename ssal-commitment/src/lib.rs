@@ -1,33 +1,68 @@
+pub mod error;
+pub mod evm;
 pub mod kzg;
+pub mod merkle;
 pub mod param;
+pub mod srs;
 pub mod vc;
 
-use ark_bn254::Bn254;
+use std::collections::BTreeMap;
+
 use ark_ec::PairingEngine;
-use ark_ff::FromBytes;
-use ark_std::test_rng;
-use param::{ProverParam, StructuredReferenceString};
-use sha2::{Digest, Sha224};
-use ssal_core::types::RawTransaction;
+use ark_ff::PrimeField;
+use ark_std::{cfg_chunks, cfg_iter, rand::RngCore};
+use error::CommitmentError;
+use kzg::KzgCommitment;
+use merkle::{Keccak256Hasher, MerkleCommitment, Sha256Hasher};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use srs::{CommitmentParam, CommitmentParamSet};
+use ssal_core::{
+    ethers_core::{types::Bytes, utils::keccak256},
+    types::*,
+};
 
 pub struct Commitment<E: PairingEngine, const N: usize> {
     commitment: E::G1Projective,
 }
 
-pub trait CommitmentScheme {
-    type ProverParam;
+/// An `(input, pos, witness)` opening of a commitment.
+pub type Opening<S> = (
+    <S as CommitmentScheme>::MessageUnit,
+    usize,
+    <S as CommitmentScheme>::Witness,
+);
+
+/// With the `parallel` feature, the transactions of a block are hashed and its chunks
+/// are committed to on the rayon thread pool, hence the `Send` and `Sync` bounds.
+pub trait CommitmentScheme: Send {
+    type ProverParam: Sync;
     type VerifierParam;
-    type MessageUnit;
+    type MessageUnit: Send + Sync;
     type Commitment;
     type Witness;
 
+    /// Map the bytes of a transaction to the input it is committed as. Defined for every
+    /// byte string.
+    fn hash_to_message_unit(bytes: &[u8]) -> Self::MessageUnit;
+
     /// Commit to a list of inputs with prover parameters
-    fn commit(pp: &Self::ProverParam, inputs: &[Self::MessageUnit]) -> Self;
+    fn commit(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, CommitmentError>
+    where
+        Self: Sized;
 
     /// Open an input at a given position
-    fn open(pp: &Self::ProverParam, inputs: &[Self::MessageUnit], pos: usize) -> Self::Witness;
+    fn open(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, CommitmentError>;
 
-    /// Verify the input/witness pair is correct
+    /// Verify the input/witness pair is correct. Openings at positions the parameters
+    /// do not cover are rejected.
     fn verify(
         &self,
         vp: &Self::VerifierParam,
@@ -36,24 +71,461 @@ pub trait CommitmentScheme {
         witness: &Self::Witness,
     ) -> bool;
 
-    fn to_string(&self) -> String;
-}
-
-pub fn get_block_commitment(block: Vec<RawTransaction>) -> Vec<u8> {
-    let mut rng = test_rng();
-    let srs = StructuredReferenceString::<Bn254, 128>::new_srs_for_testing(&mut rng);
-    let prover_param: ProverParam<Bn254, 128> = (&srs).into();
-    let message: Vec<<Bn254 as PairingEngine>::Fr> = block
-        .into_iter()
-        .map(|raw_tx| {
-            let mut hasher = Sha224::new();
-            hasher.update(raw_tx.as_ref());
-            let mut hashed_raw_tx = hasher.finalize().to_vec();
-            hashed_raw_tx.extend_from_slice(&[0; 24]);
-            <Bn254 as PairingEngine>::Fr::read(hashed_raw_tx.as_slice()).unwrap()
-        })
+    /// Verify many `(input, pos, witness)` openings of the commitment at once. Accepts
+    /// exactly when [`CommitmentScheme::verify`] accepts every opening, except with
+    /// negligible probability over `rng` for schemes that combine the checks.
+    fn batch_verify<R: RngCore>(
+        &self,
+        vp: &Self::VerifierParam,
+        openings: &[Opening<Self>],
+        _rng: &mut R,
+    ) -> bool {
+        openings
+            .iter()
+            .all(|(input, pos, witness)| self.verify(vp, input, *pos, witness))
+    }
+
+    /// Canonical compressed encoding of the commitment. For the vector and KZG
+    /// commitments this is the encoding of a single chunk; the block commitment posted on
+    /// chain is the keccak256 aggregate of the encodings of every chunk, even for a block
+    /// of one chunk.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, CommitmentError>
+    where
+        Self: Sized;
+
+    /// Canonical compressed encoding of the witness.
+    fn witness_to_bytes(witness: &Self::Witness) -> Vec<u8>;
+
+    fn witness_from_bytes(bytes: &[u8]) -> Result<Self::Witness, CommitmentError>;
+}
+
+/// `keccak256(0x00 || bytes) || keccak256(0x01 || bytes)` read as a big-endian integer
+/// and reduced modulo the order of the field. Twice as wide as the field, so the result
+/// is close to uniform, and cheap to recompute on the EVM (see [`evm`]).
+fn hash_to_field<F: PrimeField>(bytes: &[u8]) -> F {
+    let mut wide_bytes = Vec::with_capacity(64);
+    for domain in [0x00, 0x01] {
+        let mut input = Vec::with_capacity(bytes.len() + 1);
+        input.push(domain);
+        input.extend_from_slice(bytes);
+        wide_bytes.extend_from_slice(&keccak256(input));
+    }
+    F::from_be_bytes_mod_order(&wide_bytes)
+}
+
+fn block_message<S: CommitmentScheme>(block: &[RawTransaction]) -> Vec<S::MessageUnit> {
+    cfg_iter!(block)
+        .map(|raw_tx| S::hash_to_message_unit(raw_tx.as_ref()))
+        .collect()
+}
+
+/// The number of transactions committed to by each vector or KZG commitment of a block.
+pub const CHUNK_SIZE: usize = 128;
+
+/// How the transactions of a block are split between commitments.
+#[derive(Clone, Copy)]
+enum Chunking {
+    /// Chunks of at most the given number of transactions, whose commitments are
+    /// aggregated into `keccak256(chunk_commitment_0 || chunk_commitment_1 || ..)`. A block
+    /// of a single chunk is aggregated as well, so its block commitment is the hash of the
+    /// chunk commitment rather than the compressed point.
+    Aggregate(usize),
+    /// A single commitment to the whole block, which is the block commitment itself.
+    Single,
+}
+
+impl Chunking {
+    fn of(commitment_method: CommitmentMethod) -> Self {
+        match commitment_method {
+            CommitmentMethod::VectorCommitment | CommitmentMethod::Kzg => {
+                Self::Aggregate(CHUNK_SIZE)
+            }
+            CommitmentMethod::MerkleKeccak256 | CommitmentMethod::MerkleSha256 => Self::Single,
+        }
+    }
+
+    fn chunk_size(&self) -> usize {
+        match self {
+            Self::Aggregate(chunk_size) => *chunk_size,
+            Self::Single => usize::MAX,
+        }
+    }
+
+    fn aggregate<'a>(
+        &self,
+        chunk_commitments: impl ExactSizeIterator<Item = &'a [u8]>,
+    ) -> Result<Vec<u8>, CommitmentError> {
+        match self {
+            Self::Aggregate(_) => {
+                let mut bytes = Vec::new();
+                for chunk_commitment in chunk_commitments {
+                    bytes.extend_from_slice(chunk_commitment);
+                }
+                Ok(keccak256(bytes).to_vec())
+            }
+            Self::Single => {
+                let count = chunk_commitments.len();
+                match (count, chunk_commitments.map(<[u8]>::to_vec).next()) {
+                    (1, Some(chunk_commitment)) => Ok(chunk_commitment),
+                    _others => Err(CommitmentError::InvalidChunkCount(count)),
+                }
+            }
+        }
+    }
+}
+
+/// Commit to every chunk of `message`. An empty block has a single, empty chunk.
+fn commit_chunks<S: CommitmentScheme>(
+    pp: &S::ProverParam,
+    message: &[S::MessageUnit],
+    chunking: Chunking,
+) -> Result<Vec<S>, CommitmentError> {
+    if message.is_empty() {
+        return Ok(vec![S::commit(pp, message)?]);
+    }
+    cfg_chunks!(message, chunking.chunk_size())
+        .map(|chunk| S::commit(pp, chunk))
+        .collect()
+}
+
+fn block_commitment<S: CommitmentScheme>(
+    pp: &S::ProverParam,
+    chunking: Chunking,
+    block: &[RawTransaction],
+) -> Result<Vec<u8>, CommitmentError> {
+    let message = block_message::<S>(block);
+
+    let chunk_commitments: Vec<Vec<u8>> = commit_chunks::<S>(pp, &message, chunking)?
+        .iter()
+        .map(S::to_bytes)
+        .collect();
+    chunking.aggregate(chunk_commitments.iter().map(Vec::as_slice))
+}
+
+fn inclusion_proof<S: CommitmentScheme>(
+    pp: &S::ProverParam,
+    chunking: Chunking,
+    block: &[RawTransaction],
+    block_height: BlockHeight,
+    tx_order: TransactionOrder,
+) -> Result<InclusionProof, CommitmentError> {
+    let out_of_range = CommitmentError::PositionOutOfRange {
+        pos: tx_order.value(),
+        len: block.len(),
+    };
+    if tx_order.value() >= block.len() {
+        return Err(out_of_range);
+    }
+
+    let message = block_message::<S>(block);
+    let chunk_commitments: Vec<Bytes> = commit_chunks::<S>(pp, &message, chunking)?
+        .iter()
+        .map(|commitment| commitment.to_bytes().into())
         .collect();
 
-    let commitment = Commitment::<Bn254, 128>::commit(&prover_param, &message);
-    commitment.to_string().into_bytes()
+    let chunk_size = chunking.chunk_size();
+    let chunk = message
+        .chunks(chunk_size)
+        .nth(tx_order.value() / chunk_size)
+        .ok_or(out_of_range)?;
+    let witness = S::open(pp, chunk, tx_order.value() % chunk_size)?;
+    Ok(InclusionProof::new(
+        block_height,
+        tx_order,
+        chunk_commitments,
+        S::witness_to_bytes(&witness).into(),
+    ))
+}
+
+fn verify<S: CommitmentScheme>(
+    vp: &S::VerifierParam,
+    chunking: Chunking,
+    block_commitment: &[u8],
+    raw_tx: &RawTransaction,
+    proof: &InclusionProof,
+) -> Result<bool, CommitmentError> {
+    let chunk_commitments = proof.chunk_commitments();
+    if chunking.aggregate(chunk_commitments.iter().map(|bytes| bytes.as_ref()))? != block_commitment
+    {
+        return Ok(false);
+    }
+
+    let chunk_size = chunking.chunk_size();
+    let chunk_commitment = match chunk_commitments.get(proof.tx_order().value() / chunk_size) {
+        Some(chunk_commitment) => chunk_commitment,
+        None => return Ok(false),
+    };
+    let commitment = S::from_bytes(chunk_commitment)?;
+    let witness = S::witness_from_bytes(proof.witness())?;
+    Ok(commitment.verify(
+        vp,
+        &S::hash_to_message_unit(raw_tx.as_ref()),
+        proof.tx_order().value() % chunk_size,
+        &witness,
+    ))
+}
+
+/// Check many inclusion proofs into the same block, batching the openings of each chunk.
+fn verify_batch<S: CommitmentScheme>(
+    vp: &S::VerifierParam,
+    chunking: Chunking,
+    block_commitment: &[u8],
+    inclusions: &[(RawTransaction, InclusionProof)],
+) -> Result<bool, CommitmentError> {
+    let chunk_size = chunking.chunk_size();
+    let mut chunk_commitments: Option<&[Bytes]> = None;
+    let mut openings: BTreeMap<usize, Vec<Opening<S>>> = BTreeMap::new();
+    for (raw_tx, proof) in inclusions {
+        // Every proof into the block carries the same chunk commitments.
+        match chunk_commitments {
+            Some(chunk_commitments) if chunk_commitments == proof.chunk_commitments() => {}
+            Some(_) => return Ok(false),
+            None => {
+                let aggregate = chunking
+                    .aggregate(proof.chunk_commitments().iter().map(|bytes| bytes.as_ref()))?;
+                if aggregate != block_commitment {
+                    return Ok(false);
+                }
+                chunk_commitments = Some(proof.chunk_commitments());
+            }
+        }
+
+        let witness = S::witness_from_bytes(proof.witness())?;
+        openings
+            .entry(proof.tx_order().value() / chunk_size)
+            .or_default()
+            .push((
+                S::hash_to_message_unit(raw_tx.as_ref()),
+                proof.tx_order().value() % chunk_size,
+                witness,
+            ));
+    }
+
+    let chunk_commitments = match chunk_commitments {
+        Some(chunk_commitments) => chunk_commitments,
+        None => return Ok(true),
+    };
+    let mut rng = ssal_core::rand::thread_rng();
+    for (chunk_index, chunk_openings) in openings {
+        let chunk_commitment = match chunk_commitments.get(chunk_index) {
+            Some(chunk_commitment) => chunk_commitment,
+            None => return Ok(false),
+        };
+        let commitment = S::from_bytes(chunk_commitment)?;
+        if !commitment.batch_verify(vp, &chunk_openings, &mut rng) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// An operation on a block run with the [`CommitmentScheme`] of its rollup.
+trait SchemeOperation {
+    type Output;
+
+    fn run<S: CommitmentScheme>(
+        self,
+        pp: &S::ProverParam,
+        vp: &S::VerifierParam,
+        chunking: Chunking,
+    ) -> Result<Self::Output, CommitmentError>;
+}
+
+/// Run `operation` with the scheme of `commitment_method` on `curve`, loading the
+/// parameters of the curve on first use.
+fn run_with_scheme<O: SchemeOperation>(
+    params: &CommitmentParamSet,
+    curve: Curve,
+    commitment_method: CommitmentMethod,
+    operation: O,
+) -> Result<O::Output, CommitmentError> {
+    let chunking = Chunking::of(commitment_method);
+    match commitment_method {
+        CommitmentMethod::MerkleKeccak256 => {
+            operation.run::<MerkleCommitment<Keccak256Hasher>>(&(), &(), chunking)
+        }
+        CommitmentMethod::MerkleSha256 => {
+            operation.run::<MerkleCommitment<Sha256Hasher>>(&(), &(), chunking)
+        }
+        CommitmentMethod::VectorCommitment | CommitmentMethod::Kzg => {
+            let kzg = commitment_method == CommitmentMethod::Kzg;
+            match curve {
+                Curve::Bn254 => run_on_curve(params.bn254()?, kzg, chunking, operation),
+                Curve::Bls12_381 => run_on_curve(params.bls12_381()?, kzg, chunking, operation),
+                Curve::Bls12_377 => run_on_curve(params.bls12_377()?, kzg, chunking, operation),
+            }
+        }
+    }
+}
+
+fn run_on_curve<E: PairingEngine, O: SchemeOperation>(
+    param: &CommitmentParam<E>,
+    kzg: bool,
+    chunking: Chunking,
+    operation: O,
+) -> Result<O::Output, CommitmentError> {
+    if kzg {
+        operation.run::<KzgCommitment<E>>(
+            param.kzg_prover_param(),
+            param.kzg_verifier_param(),
+            chunking,
+        )
+    } else {
+        operation.run::<Commitment<E, CHUNK_SIZE>>(
+            param.prover_param(),
+            param.verifier_param(),
+            chunking,
+        )
+    }
+}
+
+struct CommitBlock<'a> {
+    block: &'a [RawTransaction],
+}
+
+impl<'a> SchemeOperation for CommitBlock<'a> {
+    type Output = Vec<u8>;
+
+    fn run<S: CommitmentScheme>(
+        self,
+        pp: &S::ProverParam,
+        _vp: &S::VerifierParam,
+        chunking: Chunking,
+    ) -> Result<Self::Output, CommitmentError> {
+        block_commitment::<S>(pp, chunking, self.block)
+    }
+}
+
+struct OpenBlock<'a> {
+    block: &'a [RawTransaction],
+    block_height: BlockHeight,
+    tx_order: TransactionOrder,
+}
+
+impl<'a> SchemeOperation for OpenBlock<'a> {
+    type Output = InclusionProof;
+
+    fn run<S: CommitmentScheme>(
+        self,
+        pp: &S::ProverParam,
+        _vp: &S::VerifierParam,
+        chunking: Chunking,
+    ) -> Result<Self::Output, CommitmentError> {
+        inclusion_proof::<S>(pp, chunking, self.block, self.block_height, self.tx_order)
+    }
+}
+
+struct VerifyInclusion<'a> {
+    block_commitment: &'a [u8],
+    raw_tx: &'a RawTransaction,
+    proof: &'a InclusionProof,
+}
+
+impl<'a> SchemeOperation for VerifyInclusion<'a> {
+    type Output = bool;
+
+    fn run<S: CommitmentScheme>(
+        self,
+        _pp: &S::ProverParam,
+        vp: &S::VerifierParam,
+        chunking: Chunking,
+    ) -> Result<Self::Output, CommitmentError> {
+        verify::<S>(vp, chunking, self.block_commitment, self.raw_tx, self.proof)
+    }
+}
+
+struct VerifyInclusions<'a> {
+    block_commitment: &'a [u8],
+    inclusions: &'a [(RawTransaction, InclusionProof)],
+}
+
+impl<'a> SchemeOperation for VerifyInclusions<'a> {
+    type Output = bool;
+
+    fn run<S: CommitmentScheme>(
+        self,
+        _pp: &S::ProverParam,
+        vp: &S::VerifierParam,
+        chunking: Chunking,
+    ) -> Result<Self::Output, CommitmentError> {
+        verify_batch::<S>(vp, chunking, self.block_commitment, self.inclusions)
+    }
+}
+
+/// Commit to `block` with the commitment method of the rollup on its curve.
+pub fn get_block_commitment(
+    params: &CommitmentParamSet,
+    curve: Curve,
+    commitment_method: CommitmentMethod,
+    block: &[RawTransaction],
+) -> Result<BlockCommitment, CommitmentError> {
+    let commitment = run_with_scheme(params, curve, commitment_method, CommitBlock { block })?;
+    Ok(BlockCommitment::new(
+        curve,
+        commitment_method,
+        commitment.into(),
+    ))
+}
+
+/// Open the commitment of `block` at the position of the transaction `tx_order`.
+pub fn get_inclusion_proof(
+    params: &CommitmentParamSet,
+    curve: Curve,
+    commitment_method: CommitmentMethod,
+    block: &[RawTransaction],
+    block_height: BlockHeight,
+    tx_order: TransactionOrder,
+) -> Result<InclusionProof, CommitmentError> {
+    run_with_scheme(
+        params,
+        curve,
+        commitment_method,
+        OpenBlock {
+            block,
+            block_height,
+            tx_order,
+        },
+    )
+}
+
+/// Check that `raw_tx` sits at the position of `proof` in the block committed to by
+/// `block_commitment`, decoding the proof with the curve and method it is tagged with.
+pub fn verify_inclusion_proof(
+    params: &CommitmentParamSet,
+    block_commitment: &BlockCommitment,
+    raw_tx: &RawTransaction,
+    proof: &InclusionProof,
+) -> Result<bool, CommitmentError> {
+    run_with_scheme(
+        params,
+        block_commitment.curve(),
+        block_commitment.commitment_method(),
+        VerifyInclusion {
+            block_commitment: block_commitment.commitment(),
+            raw_tx,
+            proof,
+        },
+    )
+}
+
+/// Check the inclusion proofs of many transactions of the block committed to by
+/// `block_commitment` at once, which is how a whole block is audited. Accepts exactly
+/// when [`verify_inclusion_proof`] accepts every proof, except with negligible
+/// probability.
+pub fn verify_inclusion_proofs(
+    params: &CommitmentParamSet,
+    block_commitment: &BlockCommitment,
+    inclusions: &[(RawTransaction, InclusionProof)],
+) -> Result<bool, CommitmentError> {
+    run_with_scheme(
+        params,
+        block_commitment.curve(),
+        block_commitment.commitment_method(),
+        VerifyInclusions {
+            block_commitment: block_commitment.commitment(),
+            inclusions,
+        },
+    )
 }
@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+
+use ark_serialize::SerializationError;
+use sha2::{Digest, Sha256};
+use ssal_core::ethers_core::utils::keccak256;
+
+use crate::{error::CommitmentError, CommitmentScheme};
+
+/// The hash function of a [`MerkleCommitment`].
+pub trait MerkleHasher {
+    fn hash(bytes: &[u8]) -> [u8; 32];
+}
+
+pub struct Keccak256Hasher;
+
+impl MerkleHasher for Keccak256Hasher {
+    fn hash(bytes: &[u8]) -> [u8; 32] {
+        keccak256(bytes)
+    }
+}
+
+pub struct Sha256Hasher;
+
+impl MerkleHasher for Sha256Hasher {
+    fn hash(bytes: &[u8]) -> [u8; 32] {
+        Sha256::digest(bytes).into()
+    }
+}
+
+/// The root of a binary Merkle tree over the inputs.
+///
+/// - leaf = H(0x00 || input)
+/// - node = H(0x01 || left || right)
+///
+/// The leaves are padded with zero hashes up to the next power of two, so the witness
+/// of every position is the list of its siblings from the leaf up and the bits of the
+/// position tell on which side each sibling is.
+pub struct MerkleCommitment<H: MerkleHasher> {
+    root: [u8; 32],
+    _hasher: PhantomData<fn() -> H>,
+}
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+impl<H: MerkleHasher> MerkleCommitment<H> {
+    fn hash_leaf(input: &[u8; 32]) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(33);
+        bytes.push(LEAF_PREFIX);
+        bytes.extend_from_slice(input);
+        H::hash(&bytes)
+    }
+
+    fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(65);
+        bytes.push(NODE_PREFIX);
+        bytes.extend_from_slice(left);
+        bytes.extend_from_slice(right);
+        H::hash(&bytes)
+    }
+
+    /// Every level of the tree below the root from the leaves up, and the root.
+    fn levels(inputs: &[[u8; 32]]) -> (Vec<Vec<[u8; 32]>>, [u8; 32]) {
+        let width = inputs.len().max(1).next_power_of_two();
+        let mut level: Vec<[u8; 32]> = inputs.iter().map(Self::hash_leaf).collect();
+        level.resize(width, [0; 32]);
+
+        let mut levels = Vec::new();
+        while let [left, right, ..] = level[..] {
+            let next_level = if level.len() == 2 {
+                vec![Self::hash_node(&left, &right)]
+            } else {
+                level
+                    .chunks_exact(2)
+                    .map(|pair| Self::hash_node(&pair[0], &pair[1]))
+                    .collect()
+            };
+            levels.push(std::mem::replace(&mut level, next_level));
+        }
+        (levels, level[0])
+    }
+
+    pub fn root(&self) -> &[u8; 32] {
+        &self.root
+    }
+}
+
+impl<H: MerkleHasher> CommitmentScheme for MerkleCommitment<H> {
+    type ProverParam = ();
+    type VerifierParam = ();
+    type MessageUnit = [u8; 32];
+    type Commitment = Self;
+    type Witness = Vec<[u8; 32]>;
+
+    fn hash_to_message_unit(bytes: &[u8]) -> Self::MessageUnit {
+        H::hash(bytes)
+    }
+
+    fn commit(
+        _pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, CommitmentError> {
+        let (_levels, root) = Self::levels(inputs);
+        Ok(Self {
+            root,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn open(
+        _pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, CommitmentError> {
+        if pos >= inputs.len() {
+            return Err(CommitmentError::PositionOutOfRange {
+                pos,
+                len: inputs.len(),
+            });
+        }
+
+        let (levels, _root) = Self::levels(inputs);
+        Ok(levels
+            .iter()
+            .enumerate()
+            .map(|(height, level)| level[(pos >> height) ^ 1])
+            .collect())
+    }
+
+    fn verify(
+        &self,
+        _vp: &Self::VerifierParam,
+        input: &Self::MessageUnit,
+        pos: usize,
+        witness: &Self::Witness,
+    ) -> bool {
+        if witness.len() < usize::BITS as usize && pos >> witness.len() != 0 {
+            return false;
+        }
+
+        let mut node = Self::hash_leaf(input);
+        for (height, sibling) in witness.iter().enumerate() {
+            node = if (pos >> height) & 1 == 0 {
+                Self::hash_node(&node, sibling)
+            } else {
+                Self::hash_node(sibling, &node)
+            };
+        }
+        node == self.root
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.root.to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, CommitmentError> {
+        Ok(Self {
+            root: bytes
+                .try_into()
+                .map_err(|_| SerializationError::InvalidData)?,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn witness_to_bytes(witness: &Self::Witness) -> Vec<u8> {
+        witness.concat()
+    }
+
+    fn witness_from_bytes(bytes: &[u8]) -> Result<Self::Witness, CommitmentError> {
+        if bytes.len() % 32 != 0 {
+            return Err(SerializationError::InvalidData.into());
+        }
+        Ok(bytes
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut sibling = [0; 32];
+                sibling.copy_from_slice(chunk);
+                sibling
+            })
+            .collect())
+    }
+}
@@ -1,6 +1,16 @@
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
 use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_poly_commit::kzg10::UniversalParams;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{rand::Rng, UniformRand, Zero};
 
+/// The first bytes of an SRS file (see [`StructuredReferenceString::write`]).
+pub const SRS_MAGIC: &[u8; 8] = b"SSALSRS1";
+
 /// A StructuredReferenceString contains three components:
 /// - g = \[ alpha * G, alpha^2 * G,     alpha^3 G,      \dots,   alpha^{n} G,
 ///           _,        alpha^{n+2} * G, alpha^{n+3} G, \dots,   alpha^{2n} G \]
@@ -33,14 +43,18 @@ pub struct VerifierParam<E: PairingEngine, const N: usize> {
 impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
     /// NOTE: If we can define a single CPU architecture on which we run the library,
     /// we can use SSE 4.2 to improve performance instead of using Rayon's parallel iterator which consumes all cores.
+    ///
+    /// With the `parallel` feature, the powers are multiplied into the generators in
+    /// parallel.
     pub fn new_srs_for_testing<R: Rng>(rng: &mut R) -> Self {
         // compute the alpha base as 1, alpha, alpha^2... alpha^{2n-1}
         // with alpha^n empty
         let alpha = E::Fr::rand(rng);
         let mut alpha_base = Vec::<E::Fr>::with_capacity(N << 1);
-        alpha_base.push(alpha);
-        for _ in 1..N << 1 {
-            alpha_base.push(alpha * alpha_base.last().unwrap())
+        let mut alpha_power = alpha;
+        for _ in 0..N << 1 {
+            alpha_base.push(alpha_power);
+            alpha_power *= alpha;
         }
         // - t  = e(alpha^{n+1} * G, H)
         let t = E::pairing(
@@ -68,6 +82,23 @@ impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
             (g, h)
         };
 
+        #[cfg(feature = "parallel")]
+        let (g, h) = {
+            use rayon::prelude::*;
+
+            // Same as the serial computation, with every scalar multiplication on the
+            // rayon thread pool.
+            let g: Vec<E::G1Projective> = alpha_base
+                .par_iter()
+                .map(|&alpha_power| E::G1Affine::prime_subgroup_generator().mul(alpha_power))
+                .collect();
+            let h: Vec<E::G2Projective> = alpha_base[..N]
+                .par_iter()
+                .map(|&alpha_power| E::G2Affine::prime_subgroup_generator().mul(alpha_power))
+                .collect();
+            (g, h)
+        };
+
         let g = E::G1Projective::batch_normalization_into_affine(&g);
         let h = E::G2Projective::batch_normalization_into_affine(&h);
 
@@ -75,6 +106,80 @@ impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
     }
 }
 
+impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
+    /// Write the SRS in the following binary format, where points are compressed
+    /// with `CanonicalSerialize`:
+    ///
+    /// | Field   | Size                  | Content                                   |
+    /// |---------|-----------------------|-------------------------------------------|
+    /// | `magic` | 8 bytes               | [`SRS_MAGIC`]                             |
+    /// | `n`     | 8 bytes               | `N` as little-endian `u64`                |
+    /// | `g`     | `2N` G1 points        | `g[N]` is the point at infinity           |
+    /// | `h`     | `N` G2 points         |                                           |
+    /// | `t`     | 1 GT element          |                                           |
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        writer.write_all(SRS_MAGIC)?;
+        writer.write_all(&(N as u64).to_le_bytes())?;
+        for g in self.g.iter() {
+            g.serialize(&mut writer)?;
+        }
+        for h in self.h.iter() {
+            h.serialize(&mut writer)?;
+        }
+        self.t.serialize(&mut writer)
+    }
+
+    /// Read an SRS written by [`StructuredReferenceString::write`] for the same `N`.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        let mut n = [0; 8];
+        reader.read_exact(&mut n)?;
+        if &magic != SRS_MAGIC || u64::from_le_bytes(n) != N as u64 {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let g = (0..N << 1)
+            .map(|_| E::G1Affine::deserialize(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        let h = (0..N)
+            .map(|_| E::G2Affine::deserialize(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        let t = E::Fqk::deserialize(&mut reader)?;
+
+        // Reject trailing bytes.
+        if reader.read(&mut [0])? != 0 {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(Self { g, h, t })
+    }
+}
+
+impl<E: PairingEngine, const N: usize> StructuredReferenceString<E, N> {
+    /// The KZG parameters for polynomials of degree up to `n`, which the SRS already holds:
+    /// - powers_of_g = \[ G, alpha * G, alpha^2 * G, \dots, alpha^{n} G \]
+    /// - beta_h = alpha * H
+    ///
+    /// Hiding commitments are not supported, so `gamma * G` is the point at infinity.
+    pub fn kzg_params(&self) -> UniversalParams<E> {
+        let mut powers_of_g = Vec::with_capacity(N + 1);
+        powers_of_g.push(E::G1Affine::prime_subgroup_generator());
+        powers_of_g.extend_from_slice(&self.g[..N]);
+
+        let h = E::G2Affine::prime_subgroup_generator();
+        let beta_h = self.h[0];
+        UniversalParams {
+            powers_of_g,
+            powers_of_gamma_g: BTreeMap::from([(0, E::G1Affine::zero())]),
+            h,
+            beta_h,
+            neg_powers_of_h: BTreeMap::new(),
+            prepared_h: h.into(),
+            prepared_beta_h: beta_h.into(),
+        }
+    }
+}
+
 impl<'a, E: PairingEngine, const N: usize> From<&'a StructuredReferenceString<E, N>>
     for ProverParam<E, N>
 {
@@ -89,7 +194,7 @@ impl<'a, E: PairingEngine, const N: usize> From<&'a StructuredReferenceString<E,
     fn from(srs: &'a StructuredReferenceString<E, N>) -> Self {
         Self {
             h: srs.h.to_vec(),
-            t: srs.t.clone(),
+            t: srs.t,
         }
     }
 }
@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::PairingEngine;
+use sha2::{Digest, Sha256};
+use ssal_core::{ethers_core::utils::hex, types::Curve};
+
+use crate::{
+    error::CommitmentError,
+    kzg::{self, KzgProverParam, KzgVerifierParam},
+    param::{ProverParam, StructuredReferenceString, VerifierParam},
+    CHUNK_SIZE,
+};
+
+/// `<path>.sha256`, in the format of `sha256sum` so that it can also be checked with
+/// `sha256sum -c`.
+pub fn checksum_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut checksum_path = path.as_ref().as_os_str().to_owned();
+    checksum_path.push(".sha256");
+    checksum_path.into()
+}
+
+/// The prover and verifier parameters of the block commitment, loaded once at startup.
+/// The vector commitment and the KZG commitment share the same SRS.
+pub struct CommitmentParam<E: PairingEngine> {
+    prover_param: ProverParam<E, CHUNK_SIZE>,
+    verifier_param: VerifierParam<E, CHUNK_SIZE>,
+    kzg_prover_param: KzgProverParam<E>,
+    kzg_verifier_param: KzgVerifierParam<E>,
+}
+
+impl<'a, E: PairingEngine> TryFrom<&'a StructuredReferenceString<E, CHUNK_SIZE>>
+    for CommitmentParam<E>
+{
+    type Error = CommitmentError;
+
+    fn try_from(srs: &'a StructuredReferenceString<E, CHUNK_SIZE>) -> Result<Self, Self::Error> {
+        let (kzg_prover_param, kzg_verifier_param) = kzg::setup(&srs.kzg_params(), CHUNK_SIZE)?;
+        Ok(Self {
+            prover_param: srs.into(),
+            verifier_param: srs.into(),
+            kzg_prover_param,
+            kzg_verifier_param,
+        })
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> CommitmentError + '_ {
+    |source| CommitmentError::Io {
+        path: path.to_owned(),
+        source,
+    }
+}
+
+impl<E: PairingEngine> CommitmentParam<E> {
+    /// Load the SRS file at `path` after checking that its hex-encoded SHA-256 is
+    /// `expected_checksum`. The expected checksum comes from the configuration of the
+    /// caller, never from next to the file, so that a replaced SRS cannot vouch for itself.
+    pub fn load(path: impl AsRef<Path>, expected_checksum: &str) -> Result<Self, CommitmentError> {
+        let path = path.as_ref();
+        let srs_bytes = fs::read(path).map_err(io_error(path))?;
+
+        let checksum = hex::encode(Sha256::digest(&srs_bytes));
+        if !checksum.eq_ignore_ascii_case(expected_checksum) {
+            return Err(CommitmentError::ChecksumMismatch {
+                path: path.to_owned(),
+                checksum,
+                expected: expected_checksum.to_owned(),
+            });
+        }
+
+        let srs = StructuredReferenceString::<E, CHUNK_SIZE>::read(srs_bytes.as_slice())?;
+        Self::try_from(&srs)
+    }
+
+    /// Write `srs` to `path` and its checksum next to it for `sha256sum -c`. Returns the
+    /// checksum, which is what the loaders of the SRS must be configured with.
+    pub fn save(
+        srs: &StructuredReferenceString<E, CHUNK_SIZE>,
+        path: impl AsRef<Path>,
+    ) -> Result<String, CommitmentError> {
+        let path = path.as_ref();
+        let mut srs_bytes = Vec::new();
+        srs.write(&mut srs_bytes)?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(io_error(directory))?;
+        }
+        fs::write(path, &srs_bytes).map_err(io_error(path))?;
+
+        let checksum = hex::encode(Sha256::digest(&srs_bytes));
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| {
+                io_error(path)(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "The SRS path has no file name",
+                ))
+            })?
+            .to_string_lossy();
+        let checksum_path = checksum_path(path);
+        fs::write(&checksum_path, format!("{}  {}\n", checksum, file_name))
+            .map_err(io_error(&checksum_path))?;
+        Ok(checksum)
+    }
+
+    pub fn prover_param(&self) -> &ProverParam<E, CHUNK_SIZE> {
+        &self.prover_param
+    }
+
+    pub fn verifier_param(&self) -> &VerifierParam<E, CHUNK_SIZE> {
+        &self.verifier_param
+    }
+
+    pub fn kzg_prover_param(&self) -> &KzgProverParam<E> {
+        &self.kzg_prover_param
+    }
+
+    pub fn kzg_verifier_param(&self) -> &KzgVerifierParam<E> {
+        &self.kzg_verifier_param
+    }
+}
+
+/// The [`CommitmentParam`] of every curve, each loaded from `<srs_dir>/<curve>.srs` the
+/// first time a rollup on that curve needs it. A curve can only be loaded once its
+/// expected checksum is configured with [`CommitmentParamSet::with_checksum`].
+pub struct CommitmentParamSet {
+    srs_dir: PathBuf,
+    checksums: HashMap<Curve, String>,
+    bn254: OnceLock<CommitmentParam<Bn254>>,
+    bls12_381: OnceLock<CommitmentParam<Bls12_381>>,
+    bls12_377: OnceLock<CommitmentParam<Bls12_377>>,
+}
+
+impl CommitmentParamSet {
+    pub fn new(srs_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            srs_dir: srs_dir.into(),
+            checksums: HashMap::new(),
+            bn254: OnceLock::new(),
+            bls12_381: OnceLock::new(),
+            bls12_377: OnceLock::new(),
+        }
+    }
+
+    /// Expect the SRS file of `curve` to have the hex-encoded SHA-256 `checksum`.
+    pub fn with_checksum(mut self, curve: Curve, checksum: impl Into<String>) -> Self {
+        self.checksums.insert(curve, checksum.into());
+        self
+    }
+
+    /// The SRS file of `curve` in `srs_dir`.
+    pub fn srs_path(srs_dir: impl AsRef<Path>, curve: Curve) -> PathBuf {
+        srs_dir.as_ref().join(format!("{}.srs", curve))
+    }
+
+    /// Load the parameters of `curve` ahead of their first use.
+    pub fn load(&self, curve: Curve) -> Result<(), CommitmentError> {
+        match curve {
+            Curve::Bn254 => self.bn254().map(|_| ()),
+            Curve::Bls12_381 => self.bls12_381().map(|_| ()),
+            Curve::Bls12_377 => self.bls12_377().map(|_| ()),
+        }
+    }
+
+    pub fn bn254(&self) -> Result<&CommitmentParam<Bn254>, CommitmentError> {
+        self.get_or_load(&self.bn254, Curve::Bn254)
+    }
+
+    pub fn bls12_381(&self) -> Result<&CommitmentParam<Bls12_381>, CommitmentError> {
+        self.get_or_load(&self.bls12_381, Curve::Bls12_381)
+    }
+
+    pub fn bls12_377(&self) -> Result<&CommitmentParam<Bls12_377>, CommitmentError> {
+        self.get_or_load(&self.bls12_377, Curve::Bls12_377)
+    }
+
+    fn get_or_load<'a, E: PairingEngine>(
+        &self,
+        param: &'a OnceLock<CommitmentParam<E>>,
+        curve: Curve,
+    ) -> Result<&'a CommitmentParam<E>, CommitmentError> {
+        if let Some(param) = param.get() {
+            return Ok(param);
+        }
+        // Concurrent first uses may both load the file, but only one is kept.
+        let expected_checksum = self
+            .checksums
+            .get(&curve)
+            .ok_or(CommitmentError::MissingChecksum(curve))?;
+        let loaded =
+            CommitmentParam::load(Self::srs_path(&self.srs_dir, curve), expected_checksum)?;
+        Ok(param.get_or_init(|| loaded))
+    }
+}
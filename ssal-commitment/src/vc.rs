@@ -1,15 +1,18 @@
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-    ops::Neg,
-};
+use std::ops::Neg;
 
 use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve};
-use ark_ff::{Field, PrimeField};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::{cfg_iter, rand::RngCore, UniformRand};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
 
 use crate::{
+    error::CommitmentError,
+    hash_to_field,
     param::{ProverParam, VerifierParam},
-    Commitment, CommitmentScheme,
+    Commitment, CommitmentScheme, Opening,
 };
 
 impl<E: PairingEngine, const N: usize> CommitmentScheme for Commitment<E, N> {
@@ -19,30 +22,43 @@ impl<E: PairingEngine, const N: usize> CommitmentScheme for Commitment<E, N> {
     type Commitment = Self;
     type Witness = E::G1Projective;
 
+    fn hash_to_message_unit(bytes: &[u8]) -> Self::MessageUnit {
+        hash_to_field(bytes)
+    }
+
     /// Commit to a list of inputs with prover parameters
-    fn commit(pp: &Self::ProverParam, inputs: &[Self::MessageUnit]) -> Self {
-        assert!(inputs.len() <= N);
+    fn commit(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+    ) -> Result<Self, CommitmentError> {
+        check_capacity::<N>(inputs)?;
 
         let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
-            inputs.iter().map(|x| x.into_repr()).collect();
-        Self {
+            cfg_iter!(inputs).map(|x| x.into_repr()).collect();
+        Ok(Self {
             commitment: VariableBaseMSM::multi_scalar_mul(&pp.g[0..inputs.len()], scalars.as_ref()),
-        }
+        })
     }
 
     /// Open an input at a given position
-    fn open(pp: &Self::ProverParam, inputs: &[Self::MessageUnit], pos: usize) -> Self::Witness {
-        assert!(inputs.len() <= N);
+    fn open(
+        pp: &Self::ProverParam,
+        inputs: &[Self::MessageUnit],
+        pos: usize,
+    ) -> Result<Self::Witness, CommitmentError> {
+        check_capacity::<N>(inputs)?;
+        check_position(inputs, pos)?;
 
         let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
-            inputs.iter().map(|x| x.into_repr()).collect();
-        VariableBaseMSM::multi_scalar_mul(
+            cfg_iter!(inputs).map(|x| x.into_repr()).collect();
+        Ok(VariableBaseMSM::multi_scalar_mul(
             pp.g[N - pos..N - pos + inputs.len()].as_ref(),
             scalars.as_ref(),
-        )
+        ))
     }
 
-    /// Verify the input/witness pair is correct
+    /// Verify the input/witness pair is correct, as the single opening of
+    /// [`Commitment::verify_combination`] with the scalar one.
     fn verify(
         &self,
         vp: &Self::VerifierParam,
@@ -50,24 +66,198 @@ impl<E: PairingEngine, const N: usize> CommitmentScheme for Commitment<E, N> {
         pos: usize,
         witness: &Self::Witness,
     ) -> bool {
-        let input_inverse = input.inverse().unwrap();
+        if pos >= N {
+            return false;
+        }
+
+        self.verify_combination(vp, &[(*input, pos)], &[E::Fr::one()], witness)
+    }
+
+    /// Combine the openings with random scalars `r_k` into the single equation of
+    /// [`Commitment::verify_combination`] with `W = sum r_k * W_k`, which takes two
+    /// pairings however many openings there are.
+    fn batch_verify<R: RngCore>(
+        &self,
+        vp: &Self::VerifierParam,
+        openings: &[Opening<Self>],
+        rng: &mut R,
+    ) -> bool {
+        if openings.iter().any(|(_, pos, _)| *pos >= N) {
+            return false;
+        }
+
+        let scalars: Vec<E::Fr> = openings.iter().map(|_| E::Fr::rand(rng)).collect();
+        let witnesses: Vec<E::G1Projective> =
+            openings.iter().map(|(_, _, witness)| *witness).collect();
+        let witness = combine::<E>(&witnesses, &scalars);
+        let openings: Vec<(E::Fr, usize)> = openings
+            .iter()
+            .map(|(input, pos, _)| (*input, *pos))
+            .collect();
+        self.verify_combination(vp, &openings, &scalars, &witness)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        serialize_point::<E>(&self.commitment)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, CommitmentError> {
+        Ok(Self {
+            commitment: deserialize_point::<E>(bytes)?,
+        })
+    }
+
+    fn witness_to_bytes(witness: &Self::Witness) -> Vec<u8> {
+        serialize_point::<E>(witness)
+    }
+
+    fn witness_from_bytes(bytes: &[u8]) -> Result<Self::Witness, CommitmentError> {
+        Ok(deserialize_point::<E>(bytes)?)
+    }
+}
+
+impl<E: PairingEngine, const N: usize> Commitment<E, N> {
+    /// Open the inputs at every position of `positions`, for example a contiguous range
+    /// of transactions, with a single witness `W = sum t_k * W_k` of constant size. The
+    /// scalars `t_k` are hashed from the commitment and the opened inputs, so that the
+    /// witness cannot be reused for other inputs.
+    pub fn open_aggregated(
+        pp: &ProverParam<E, N>,
+        inputs: &[E::Fr],
+        positions: &[usize],
+    ) -> Result<E::G1Projective, CommitmentError> {
+        check_capacity::<N>(inputs)?;
+        for pos in positions {
+            check_position(inputs, *pos)?;
+        }
+
+        let commitment = Self::commit(pp, inputs)?;
+        let openings: Vec<(E::Fr, usize)> =
+            positions.iter().map(|pos| (inputs[*pos], *pos)).collect();
+        let scalars = aggregation_scalars::<E>(&commitment.commitment, &openings);
+        let witnesses = positions
+            .iter()
+            .map(|pos| Self::open(pp, inputs, *pos))
+            .collect::<Result<Vec<E::G1Projective>, _>>()?;
+        Ok(combine::<E>(&witnesses, &scalars))
+    }
+
+    /// Verify the `(input, pos)` pairs against a witness of [`Commitment::open_aggregated`].
+    pub fn verify_aggregated(
+        &self,
+        vp: &VerifierParam<E, N>,
+        openings: &[(E::Fr, usize)],
+        witness: &E::G1Projective,
+    ) -> bool {
+        if openings.iter().any(|(_, pos)| *pos >= N) {
+            return false;
+        }
+
+        let scalars = aggregation_scalars::<E>(&self.commitment, openings);
+        self.verify_combination(vp, openings, &scalars, witness)
+    }
+
+    /// Check `e(C, sum s_k * h[N - pos_k - 1]) * e(-W, H) = t^(sum s_k * m_k)`, which
+    /// holds for `W = sum s_k * W_k` when every `W_k` opens `m_k` at `pos_k`.
+    fn verify_combination(
+        &self,
+        vp: &VerifierParam<E, N>,
+        openings: &[(E::Fr, usize)],
+        scalars: &[E::Fr],
+        witness: &E::G1Projective,
+    ) -> bool {
+        let exponent = openings
+            .iter()
+            .zip(scalars.iter())
+            .fold(E::Fr::zero(), |sum, ((input, _), s)| sum + *s * input);
+        let h: Vec<E::G2Affine> = openings.iter().map(|(_, pos)| vp.h[N - pos - 1]).collect();
+        let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+            scalars.iter().map(|s| s.into_repr()).collect();
+        let h_combination = VariableBaseMSM::multi_scalar_mul(&h, &scalars).into_affine();
 
-        let com = self
-            .commitment
-            .mul(&input_inverse.into_repr())
-            .into_affine();
-        let proof = witness.mul(input_inverse.neg().into_repr()).into_affine();
         let pairing_prod_inputs = vec![
-            (com.into(), vp.h[N - pos - 1].into()),
-            (proof.into(), E::G2Affine::prime_subgroup_generator().into()),
+            (self.commitment.into_affine().into(), h_combination.into()),
+            (
+                witness.neg().into_affine().into(),
+                E::G2Affine::prime_subgroup_generator().into(),
+            ),
         ];
-        E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t
+        E::product_of_pairings(pairing_prod_inputs.iter()) == vp.t.pow(exponent.into_repr())
+    }
+}
+
+fn check_capacity<const N: usize>(inputs: &[impl Sized]) -> Result<(), CommitmentError> {
+    if inputs.len() > N {
+        return Err(CommitmentError::TooManyInputs {
+            len: inputs.len(),
+            capacity: N,
+        });
+    }
+    Ok(())
+}
+
+fn check_position(inputs: &[impl Sized], pos: usize) -> Result<(), CommitmentError> {
+    if pos >= inputs.len() {
+        return Err(CommitmentError::PositionOutOfRange {
+            pos,
+            len: inputs.len(),
+        });
     }
+    Ok(())
+}
+
+/// `sum s_k * W_k`
+fn combine<E: PairingEngine>(witnesses: &[E::G1Projective], scalars: &[E::Fr]) -> E::G1Projective {
+    let witnesses = E::G1Projective::batch_normalization_into_affine(witnesses);
+    let scalars: Vec<<E::Fr as PrimeField>::BigInt> =
+        scalars.iter().map(|s| s.into_repr()).collect();
+    VariableBaseMSM::multi_scalar_mul(&witnesses, &scalars)
+}
+
+/// `t_k = sha256(seed || k)` reduced into the field, where
+/// `seed = sha256(C || pos_0 || m_0 || pos_1 || m_1 || ..)`.
+fn aggregation_scalars<E: PairingEngine>(
+    commitment: &E::G1Projective,
+    openings: &[(E::Fr, usize)],
+) -> Vec<E::Fr> {
+    let mut hasher = Sha256::new();
+    hasher.update(serialize_point::<E>(commitment));
+    for (input, pos) in openings {
+        hasher.update((*pos as u64).to_le_bytes());
+        let mut input_bytes = Vec::with_capacity(input.serialized_size());
+        // Writing into a `Vec` cannot fail.
+        input.serialize(&mut input_bytes).unwrap();
+        hasher.update(input_bytes);
+    }
+    let seed = hasher.finalize();
+
+    (0..openings.len())
+        .map(|k| {
+            let mut hasher = Sha256::new();
+            hasher.update(seed);
+            hasher.update((k as u64).to_le_bytes());
+            E::Fr::from_le_bytes_mod_order(&hasher.finalize())
+        })
+        .collect()
+}
+
+/// Compressed encoding of the affine point, which unlike the projective coordinates
+/// is unique.
+pub(crate) fn serialize_point<E: PairingEngine>(point: &E::G1Projective) -> Vec<u8> {
+    let point = point.into_affine();
+    let mut bytes = Vec::with_capacity(point.serialized_size());
+    // Writing into a `Vec` cannot fail.
+    point.serialize(&mut bytes).unwrap();
+    bytes
+}
 
-    fn to_string(&self) -> String {
-        let mut hasher = DefaultHasher::new();
-        self.commitment.hash(&mut hasher);
-        // println!("Hash is {:x}!", hasher.finish());
-        hasher.finish().to_string()
+pub(crate) fn deserialize_point<E: PairingEngine>(
+    mut bytes: &[u8],
+) -> Result<E::G1Projective, SerializationError> {
+    let point = E::G1Affine::deserialize(&mut bytes)?;
+    // Reject trailing bytes so that every point has exactly one encoding.
+    if !bytes.is_empty() {
+        return Err(SerializationError::InvalidData);
     }
+    Ok(point.into_projective())
 }
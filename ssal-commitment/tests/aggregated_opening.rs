@@ -0,0 +1,47 @@
+use ark_bn254::Bn254;
+use ark_ec::PairingEngine;
+use ark_std::{test_rng, UniformRand};
+use ssal_commitment::{
+    param::{ProverParam, StructuredReferenceString, VerifierParam},
+    Commitment, CommitmentScheme,
+};
+
+type Fr = <Bn254 as PairingEngine>::Fr;
+
+const N: usize = 16;
+
+#[test]
+fn aggregated_opening_of_a_range() {
+    let mut rng = test_rng();
+    let srs = StructuredReferenceString::<Bn254, N>::new_srs_for_testing(&mut rng);
+    let pp = ProverParam::from(&srs);
+    let vp = VerifierParam::from(&srs);
+
+    let inputs: Vec<Fr> = (0..12).map(|_| Fr::rand(&mut rng)).collect();
+    let commitment = Commitment::<Bn254, N>::commit(&pp, &inputs).unwrap();
+
+    let positions: Vec<usize> = (3..9).collect();
+    let witness = Commitment::open_aggregated(&pp, &inputs, &positions).unwrap();
+    let mut openings: Vec<(Fr, usize)> = positions.iter().map(|pos| (inputs[*pos], *pos)).collect();
+    assert!(commitment.verify_aggregated(&vp, &openings, &witness));
+
+    // A single position is an ordinary opening scaled by its hashed scalar.
+    let single = Commitment::open_aggregated(&pp, &inputs, &[11]).unwrap();
+    assert!(commitment.verify_aggregated(&vp, &[(inputs[11], 11)], &single));
+
+    // The witness does not open a different input, position or subset of the range.
+    openings[2].0 += Fr::from(1u64);
+    assert!(!commitment.verify_aggregated(&vp, &openings, &witness));
+    openings[2].0 = inputs[positions[2]];
+
+    openings[2].1 = 10;
+    assert!(!commitment.verify_aggregated(&vp, &openings, &witness));
+    openings[2].1 = positions[2];
+
+    assert!(!commitment.verify_aggregated(&vp, &openings[1..], &witness));
+    assert!(!commitment.verify_aggregated(&vp, &[(inputs[11], 11)], &witness));
+
+    // Nor is it valid for another commitment.
+    let other = Commitment::<Bn254, N>::commit(&pp, &inputs[1..]).unwrap();
+    assert!(!other.verify_aggregated(&vp, &openings, &witness));
+}
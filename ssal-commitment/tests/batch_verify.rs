@@ -0,0 +1,146 @@
+use ark_bn254::Bn254;
+use ark_ec::PairingEngine;
+use ark_std::{test_rng, UniformRand};
+use ssal_commitment::{
+    kzg::{self, KzgCommitment},
+    param::{ProverParam, StructuredReferenceString, VerifierParam},
+    srs::{CommitmentParam, CommitmentParamSet},
+    Commitment, CommitmentScheme, CHUNK_SIZE,
+};
+use ssal_core::types::*;
+
+type Fr = <Bn254 as PairingEngine>::Fr;
+
+const N: usize = 16;
+
+/// Open every position of a commitment to random inputs.
+fn openings<S: CommitmentScheme<MessageUnit = Fr>>(
+    pp: &S::ProverParam,
+    len: usize,
+) -> (S, Vec<(Fr, usize, S::Witness)>) {
+    let mut rng = test_rng();
+    let inputs: Vec<Fr> = (0..len).map(|_| Fr::rand(&mut rng)).collect();
+    let commitment = S::commit(pp, &inputs).unwrap();
+    let openings = inputs
+        .iter()
+        .enumerate()
+        .map(|(pos, input)| (*input, pos, S::open(pp, &inputs, pos).unwrap()))
+        .collect();
+    (commitment, openings)
+}
+
+/// Batch verification must agree with verifying every opening on its own, both for
+/// valid openings and after tampering with any single one of them.
+fn assert_batch_matches_per_item<S: CommitmentScheme<MessageUnit = Fr>>(
+    commitment: &S,
+    vp: &S::VerifierParam,
+    openings: Vec<(Fr, usize, S::Witness)>,
+) {
+    let mut rng = test_rng();
+    let per_item = |openings: &[(Fr, usize, S::Witness)]| {
+        openings
+            .iter()
+            .all(|(input, pos, witness)| commitment.verify(vp, input, *pos, witness))
+    };
+
+    assert!(per_item(&openings));
+    assert!(commitment.batch_verify(vp, &openings, &mut rng));
+
+    let mut openings = openings;
+    for index in [0, openings.len() / 2, openings.len() - 1] {
+        let input = openings[index].0;
+        openings[index].0 = input + Fr::from(1u64);
+        assert!(!per_item(&openings));
+        assert!(!commitment.batch_verify(vp, &openings, &mut rng));
+        openings[index].0 = input;
+    }
+
+    // A valid opening claimed at another position.
+    let pos = openings[0].1;
+    openings[0].1 = openings[1].1;
+    assert!(!per_item(&openings));
+    assert!(!commitment.batch_verify(vp, &openings, &mut rng));
+    openings[0].1 = pos;
+
+    assert!(commitment.batch_verify(vp, &openings, &mut rng));
+}
+
+#[test]
+fn vector_commitment_batch_verify_matches_per_item_verify() {
+    let srs = StructuredReferenceString::<Bn254, N>::new_srs_for_testing(&mut test_rng());
+    let pp = ProverParam::from(&srs);
+    let vp = VerifierParam::from(&srs);
+
+    for len in [2, 5, N] {
+        let (commitment, openings) = openings::<Commitment<Bn254, N>>(&pp, len);
+        assert_batch_matches_per_item(&commitment, &vp, openings);
+    }
+}
+
+#[test]
+fn kzg_batch_verify_matches_per_item_verify() {
+    let srs = StructuredReferenceString::<Bn254, N>::new_srs_for_testing(&mut test_rng());
+    let (pp, vp) = kzg::setup(&srs.kzg_params(), N).unwrap();
+
+    let (commitment, openings) = openings::<KzgCommitment<Bn254>>(&pp, N);
+    assert_batch_matches_per_item(&commitment, &vp, openings);
+}
+
+#[test]
+fn block_audit_matches_per_proof_verification() {
+    let srs_dir = std::env::temp_dir().join(format!("ssal-batch-verify-{}", std::process::id()));
+    let srs = StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(&mut test_rng());
+    let checksum =
+        CommitmentParam::save(&srs, CommitmentParamSet::srs_path(&srs_dir, Curve::Bn254)).unwrap();
+    let params = CommitmentParamSet::new(&srs_dir).with_checksum(Curve::Bn254, checksum);
+
+    let block: Vec<RawTransaction> = (0..CHUNK_SIZE + 20)
+        .map(|index| RawTransaction::from(index.to_string()))
+        .collect();
+    for commitment_method in [
+        CommitmentMethod::VectorCommitment,
+        CommitmentMethod::Kzg,
+        CommitmentMethod::MerkleKeccak256,
+    ] {
+        let block_commitment =
+            ssal_commitment::get_block_commitment(&params, Curve::Bn254, commitment_method, &block)
+                .unwrap();
+        let mut inclusions: Vec<(RawTransaction, InclusionProof)> = (0..block.len())
+            .step_by(9)
+            .map(|tx_order| {
+                let proof = ssal_commitment::get_inclusion_proof(
+                    &params,
+                    Curve::Bn254,
+                    commitment_method,
+                    &block,
+                    BlockHeight::from(1),
+                    TransactionOrder::from(tx_order),
+                )
+                .unwrap();
+                (block[tx_order].clone(), proof)
+            })
+            .collect();
+
+        let per_proof = |inclusions: &[(RawTransaction, InclusionProof)]| {
+            inclusions.iter().all(|(raw_tx, proof)| {
+                ssal_commitment::verify_inclusion_proof(&params, &block_commitment, raw_tx, proof)
+                    .unwrap()
+            })
+        };
+        assert!(per_proof(&inclusions));
+        assert!(
+            ssal_commitment::verify_inclusion_proofs(&params, &block_commitment, &inclusions)
+                .unwrap()
+        );
+
+        let last = inclusions.len() - 1;
+        inclusions[last].0 = RawTransaction::from("tampered");
+        assert!(!per_proof(&inclusions));
+        assert!(
+            !ssal_commitment::verify_inclusion_proofs(&params, &block_commitment, &inclusions)
+                .unwrap()
+        );
+    }
+
+    std::fs::remove_dir_all(&srs_dir).unwrap();
+}
@@ -0,0 +1,150 @@
+use std::path::PathBuf;
+
+use ark_bn254::Bn254;
+use ark_std::test_rng;
+use ssal_commitment::{
+    param::StructuredReferenceString,
+    srs::{CommitmentParam, CommitmentParamSet},
+    CHUNK_SIZE,
+};
+use ssal_core::{ethers_core::utils::keccak256, types::*};
+
+const CHUNKED_METHODS: [CommitmentMethod; 2] =
+    [CommitmentMethod::VectorCommitment, CommitmentMethod::Kzg];
+
+/// An SRS in a temporary directory, removed on drop.
+struct Params {
+    srs_dir: PathBuf,
+    params: CommitmentParamSet,
+}
+
+impl Params {
+    fn new(name: &str) -> Self {
+        let srs_dir =
+            std::env::temp_dir().join(format!("ssal-chunking-{}-{}", name, std::process::id()));
+        let srs =
+            StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(&mut test_rng());
+        let checksum =
+            CommitmentParam::save(&srs, CommitmentParamSet::srs_path(&srs_dir, Curve::Bn254))
+                .unwrap();
+        Self {
+            params: CommitmentParamSet::new(&srs_dir).with_checksum(Curve::Bn254, checksum),
+            srs_dir,
+        }
+    }
+
+    fn commit(
+        &self,
+        commitment_method: CommitmentMethod,
+        block: &[RawTransaction],
+    ) -> BlockCommitment {
+        ssal_commitment::get_block_commitment(&self.params, Curve::Bn254, commitment_method, block)
+            .unwrap()
+    }
+
+    fn open(
+        &self,
+        commitment_method: CommitmentMethod,
+        block: &[RawTransaction],
+        tx_order: usize,
+    ) -> InclusionProof {
+        ssal_commitment::get_inclusion_proof(
+            &self.params,
+            Curve::Bn254,
+            commitment_method,
+            block,
+            BlockHeight::from(1),
+            TransactionOrder::from(tx_order),
+        )
+        .unwrap()
+    }
+
+    fn verify(
+        &self,
+        block_commitment: &BlockCommitment,
+        raw_tx: &RawTransaction,
+        proof: &InclusionProof,
+    ) -> bool {
+        ssal_commitment::verify_inclusion_proof(&self.params, block_commitment, raw_tx, proof)
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for Params {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.srs_dir);
+    }
+}
+
+fn block(len: usize) -> Vec<RawTransaction> {
+    (0..len)
+        .map(|index| RawTransaction::from(format!("transaction {}", index)))
+        .collect()
+}
+
+/// `keccak256(chunk_commitment_0 || chunk_commitment_1 || ..)`.
+fn aggregate(proof: &InclusionProof) -> Vec<u8> {
+    keccak256(proof.chunk_commitments().concat()).to_vec()
+}
+
+#[test]
+fn blocks_of_several_chunks_round_trip() {
+    let params = Params::new("round-trip");
+    let block = block(2 * CHUNK_SIZE + 5);
+
+    for commitment_method in CHUNKED_METHODS {
+        let block_commitment = params.commit(commitment_method, &block);
+        for tx_order in [
+            0,
+            CHUNK_SIZE - 1,
+            CHUNK_SIZE,
+            CHUNK_SIZE + 1,
+            block.len() - 1,
+        ] {
+            let proof = params.open(commitment_method, &block, tx_order);
+            assert_eq!(proof.chunk_commitments().len(), 3);
+            assert_eq!(block_commitment.commitment().as_ref(), aggregate(&proof));
+            assert!(params.verify(&block_commitment, &block[tx_order], &proof));
+
+            // The transaction at the same position of another chunk is not included.
+            let other_chunk = (tx_order + CHUNK_SIZE) % (3 * CHUNK_SIZE);
+            if let Some(raw_tx) = block.get(other_chunk) {
+                assert!(!params.verify(&block_commitment, raw_tx, &proof));
+            }
+        }
+
+        // A witness checked against the commitment of another chunk is rejected.
+        let proof = params.open(commitment_method, &block, CHUNK_SIZE + 1);
+        let mut chunk_commitments = proof.chunk_commitments().to_vec();
+        chunk_commitments.swap(0, 1);
+        let swapped = InclusionProof::new(
+            proof.block_height().clone(),
+            proof.tx_order().clone(),
+            chunk_commitments,
+            proof.witness().clone(),
+        );
+        assert!(!params.verify(&block_commitment, &block[CHUNK_SIZE + 1], &swapped));
+    }
+}
+
+#[test]
+fn single_chunk_blocks_are_aggregated_too() {
+    let params = Params::new("single-chunk");
+    let block = block(3);
+
+    for commitment_method in CHUNKED_METHODS {
+        let block_commitment = params.commit(commitment_method, &block);
+        let proof = params.open(commitment_method, &block, 2);
+        assert_eq!(proof.chunk_commitments().len(), 1);
+
+        // The block commitment is the hash of the only chunk commitment, not the
+        // compressed point itself.
+        assert_eq!(block_commitment.commitment().len(), 32);
+        assert_eq!(block_commitment.commitment().as_ref(), aggregate(&proof));
+        assert_ne!(
+            block_commitment.commitment().as_ref(),
+            proof.chunk_commitments()[0].as_ref()
+        );
+        assert!(params.verify(&block_commitment, &block[2], &proof));
+    }
+}
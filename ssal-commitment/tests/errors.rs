@@ -0,0 +1,71 @@
+use ark_bn254::Bn254;
+use ark_ec::PairingEngine;
+use ark_ff::Zero;
+use ark_std::{test_rng, UniformRand};
+use ssal_commitment::{
+    error::CommitmentError,
+    kzg::{self, KzgCommitment},
+    param::{ProverParam, StructuredReferenceString, VerifierParam},
+    Commitment, CommitmentScheme,
+};
+
+type Fr = <Bn254 as PairingEngine>::Fr;
+
+const N: usize = 8;
+
+#[test]
+fn vector_commitment_returns_errors_instead_of_panicking() {
+    let mut rng = test_rng();
+    let srs = StructuredReferenceString::<Bn254, N>::new_srs_for_testing(&mut rng);
+    let pp = ProverParam::from(&srs);
+    let vp = VerifierParam::from(&srs);
+
+    let too_many: Vec<Fr> = (0..N + 1).map(|_| Fr::rand(&mut rng)).collect();
+    assert!(matches!(
+        Commitment::<Bn254, N>::commit(&pp, &too_many),
+        Err(CommitmentError::TooManyInputs { len, capacity: N }) if len == N + 1
+    ));
+
+    // A zero input, which a transaction may hash to, is committed and opened like any other.
+    let mut inputs: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+    inputs[2] = Fr::zero();
+    let commitment = Commitment::<Bn254, N>::commit(&pp, &inputs).unwrap();
+    let witness = Commitment::open(&pp, &inputs, 2).unwrap();
+    assert!(commitment.verify(&vp, &Fr::zero(), 2, &witness));
+    assert!(!commitment.verify(&vp, &Fr::zero(), 1, &witness));
+    assert!(!commitment.verify(&vp, &Fr::zero(), N, &witness));
+
+    assert!(matches!(
+        Commitment::open(&pp, &inputs, 4),
+        Err(CommitmentError::PositionOutOfRange { pos: 4, len: 4 })
+    ));
+    assert!(matches!(
+        Commitment::open_aggregated(&pp, &inputs, &[0, 7]),
+        Err(CommitmentError::PositionOutOfRange { pos: 7, len: 4 })
+    ));
+    assert!(matches!(
+        Commitment::<Bn254, N>::witness_from_bytes(&[0xff; 7]),
+        Err(CommitmentError::Serialization(_))
+    ));
+}
+
+#[test]
+fn kzg_returns_errors_instead_of_panicking() {
+    let mut rng = test_rng();
+    let srs = StructuredReferenceString::<Bn254, N>::new_srs_for_testing(&mut rng);
+    assert!(matches!(
+        kzg::setup(&srs.kzg_params(), 2 * N),
+        Err(CommitmentError::DegreeTooLarge { .. })
+    ));
+
+    let (pp, _vp) = kzg::setup(&srs.kzg_params(), N).unwrap();
+    let too_many: Vec<Fr> = (0..N + 1).map(|_| Fr::rand(&mut rng)).collect();
+    assert!(matches!(
+        KzgCommitment::<Bn254>::commit(&pp, &too_many),
+        Err(CommitmentError::TooManyInputs { .. })
+    ));
+    assert!(matches!(
+        KzgCommitment::<Bn254>::open(&pp, &too_many[..3], 3),
+        Err(CommitmentError::PositionOutOfRange { pos: 3, len: 3 })
+    ));
+}
@@ -0,0 +1,458 @@
+use std::path::{Path, PathBuf};
+
+use ark_bn254::Bn254;
+use ark_std::test_rng;
+use ethers_solc::Solc;
+use revm::{
+    primitives::{Address, ExecutionResult, Output, TransactTo},
+    InMemoryDB, EVM,
+};
+use ssal_commitment::{
+    evm,
+    param::StructuredReferenceString,
+    srs::{CommitmentParam, CommitmentParamSet},
+    CHUNK_SIZE,
+};
+use ssal_core::{
+    ethers_core::{
+        abi::{self, ParamType, Token},
+        types::{U256, U512},
+        utils::{hex, keccak256},
+    },
+    types::*,
+};
+
+/// A block of two chunks, with an SRS in a temporary directory.
+struct Fixture {
+    srs_dir: PathBuf,
+    params: CommitmentParamSet,
+    block: Vec<RawTransaction>,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Self {
+        let srs_dir =
+            std::env::temp_dir().join(format!("ssal-evm-{}-{}", name, std::process::id()));
+        let srs =
+            StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(&mut test_rng());
+        let checksum =
+            CommitmentParam::save(&srs, CommitmentParamSet::srs_path(&srs_dir, Curve::Bn254))
+                .unwrap();
+        Self {
+            params: CommitmentParamSet::new(&srs_dir).with_checksum(Curve::Bn254, checksum),
+            srs_dir,
+            block: (0..CHUNK_SIZE + 2)
+                .map(|index| RawTransaction::from(format!("transaction {}", index)))
+                .collect(),
+        }
+    }
+
+    fn commit(&self, commitment_method: CommitmentMethod) -> BlockCommitment {
+        ssal_commitment::get_block_commitment(
+            &self.params,
+            Curve::Bn254,
+            commitment_method,
+            &self.block,
+        )
+        .unwrap()
+    }
+
+    fn open(&self, commitment_method: CommitmentMethod, tx_order: usize) -> InclusionProof {
+        ssal_commitment::get_inclusion_proof(
+            &self.params,
+            Curve::Bn254,
+            commitment_method,
+            &self.block,
+            BlockHeight::from(1),
+            TransactionOrder::from(tx_order),
+        )
+        .unwrap()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.srs_dir);
+    }
+}
+
+fn new_evm() -> EVM<InMemoryDB> {
+    let mut evm = revm::new();
+    evm.database(InMemoryDB::default());
+    evm
+}
+
+fn call(evm: &mut EVM<InMemoryDB>, to: Address, data: Vec<u8>) -> Vec<u8> {
+    evm.env.tx.transact_to = TransactTo::Call(to);
+    evm.env.tx.data = data.into();
+    match evm.transact_commit().unwrap() {
+        ExecutionResult::Success { output, .. } => output.into_data().to_vec(),
+        result => panic!("The call failed: {:?}", result),
+    }
+}
+
+fn deploy(evm: &mut EVM<InMemoryDB>, bytecode: Vec<u8>) -> Address {
+    evm.env.tx.transact_to = TransactTo::create();
+    evm.env.tx.data = bytecode.into();
+    match evm.transact_commit().unwrap() {
+        ExecutionResult::Success {
+            output: Output::Create(_, Some(address)),
+            ..
+        } => address,
+        result => panic!("The deployment failed: {:?}", result),
+    }
+}
+
+/// The value of `uint256 internal constant <name> = <value>;` in the generated source.
+fn constant(source: &str, name: &str) -> U256 {
+    let declaration = format!("constant {} = ", name);
+    let start = source.find(&declaration).unwrap() + declaration.len();
+    let end = start + source[start..].find(';').unwrap();
+    U256::from_dec_str(&source[start..end]).unwrap()
+}
+
+fn powers_of_h(source: &str) -> Vec<u8> {
+    let declaration = "POWERS_OF_H = hex\"";
+    let start = source.find(declaration).unwrap() + declaration.len();
+    let end = start + source[start..].find('"').unwrap();
+    hex::decode(&source[start..end]).unwrap()
+}
+
+fn words(words: &[U256]) -> Vec<u8> {
+    words
+        .iter()
+        .flat_map(|word| {
+            let mut bytes = [0; 32];
+            word.to_big_endian(&mut bytes);
+            bytes
+        })
+        .collect()
+}
+
+fn point(bytes: &[u8]) -> [U256; 2] {
+    [
+        U256::from_big_endian(&bytes[..32]),
+        U256::from_big_endian(&bytes[32..64]),
+    ]
+}
+
+fn prefixed(prefix: u8, bytes: &[u8]) -> Vec<u8> {
+    [&[prefix], bytes].concat()
+}
+
+fn mul_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    ((U512::from(a) * U512::from(b)) % U512::from(modulus))
+        .try_into()
+        .unwrap()
+}
+
+/// The EVM side of `Bn254` in the generated source, on top of the revm precompiles.
+struct Bn254Precompiles<'a> {
+    evm: EVM<InMemoryDB>,
+    source: &'a str,
+}
+
+impl<'a> Bn254Precompiles<'a> {
+    fn add(&mut self, a: [U256; 2], b: [U256; 2]) -> [U256; 2] {
+        point(&call(
+            &mut self.evm,
+            Address::with_last_byte(0x06),
+            words(&[a[0], a[1], b[0], b[1]]),
+        ))
+    }
+
+    fn mul(&mut self, a: [U256; 2], scalar: U256) -> [U256; 2] {
+        point(&call(
+            &mut self.evm,
+            Address::with_last_byte(0x07),
+            words(&[a[0], a[1], scalar]),
+        ))
+    }
+
+    fn pairing(&mut self, input: &[U256]) -> bool {
+        let output = call(&mut self.evm, Address::with_last_byte(0x08), words(input));
+        U256::from_big_endian(&output) == U256::one()
+    }
+
+    fn negate(&self, point: [U256; 2]) -> [U256; 2] {
+        if point == [U256::zero(); 2] {
+            return point;
+        }
+        [point[0], constant(self.source, "P") - point[1]]
+    }
+
+    fn hash_to_field(&self, raw_tx: &RawTransaction) -> U256 {
+        let r = constant(self.source, "R");
+        let high = U256::from_big_endian(&keccak256(prefixed(0x00, raw_tx.as_ref())));
+        let low = U256::from_big_endian(&keccak256(prefixed(0x01, raw_tx.as_ref())));
+        let high = mul_mod(high, constant(self.source, "R_2_256"), r);
+        ((U512::from(high) + U512::from(low)) % U512::from(r))
+            .try_into()
+            .unwrap()
+    }
+
+    fn aggregate(&self, points: &[[U256; 2]]) -> [u8; 32] {
+        let p = constant(self.source, "P");
+        let compressed: Vec<u8> = points
+            .iter()
+            .flat_map(|point| {
+                let mut bytes = [0; 32];
+                if *point == [U256::zero(); 2] {
+                    bytes[31] = 0x40;
+                } else {
+                    point[0].to_little_endian(&mut bytes);
+                    if point[1] > (p - 1) / 2 {
+                        bytes[31] |= 0x80;
+                    }
+                }
+                bytes
+            })
+            .collect();
+        keccak256(compressed)
+    }
+}
+
+fn decode_points(proof: &[u8]) -> (Vec<[U256; 2]>, [U256; 2]) {
+    let uint_pair = ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2);
+    let to_point = |token: Token| {
+        let words = token.into_fixed_array().unwrap();
+        [
+            words[0].clone().into_uint().unwrap(),
+            words[1].clone().into_uint().unwrap(),
+        ]
+    };
+    let mut tokens = abi::decode(
+        &[ParamType::Array(Box::new(uint_pair.clone())), uint_pair],
+        proof,
+    )
+    .unwrap();
+    let witness = to_point(tokens.pop().unwrap());
+    let chunk_commitments = tokens
+        .pop()
+        .unwrap()
+        .into_array()
+        .unwrap()
+        .into_iter()
+        .map(to_point)
+        .collect();
+    (chunk_commitments, witness)
+}
+
+/// Run the checks of the generated vector and KZG verifiers step by step, with the
+/// constants of the generated source, the ABI-encoded proofs and the bn254 precompiles
+/// of revm.
+#[test]
+fn evm_encoding_passes_the_precompile_checks() {
+    let fixture = Fixture::new("precompile");
+
+    for commitment_method in [CommitmentMethod::VectorCommitment, CommitmentMethod::Kzg] {
+        let source =
+            evm::solidity_verifier(&fixture.params, Curve::Bn254, commitment_method).unwrap();
+        let mut bn254 = Bn254Precompiles {
+            evm: new_evm(),
+            source: &source,
+        };
+        let block_commitment = fixture.commit(commitment_method);
+
+        for tx_order in [0, 5, CHUNK_SIZE + 1] {
+            let proof = fixture.open(commitment_method, tx_order);
+            let (chunk_commitments, witness) =
+                decode_points(&evm::encode_inclusion_proof(&block_commitment, &proof).unwrap());
+            assert_eq!(
+                bn254.aggregate(&chunk_commitments).as_slice(),
+                block_commitment.commitment(),
+            );
+
+            let commitment = chunk_commitments[tx_order / CHUNK_SIZE];
+            let pos = tx_order % CHUNK_SIZE;
+            for (raw_tx, expected) in [
+                (&fixture.block[tx_order], true),
+                (&RawTransaction::from("tampered"), false),
+            ] {
+                let input = bn254.hash_to_field(raw_tx);
+                let neg_witness = bn254.negate(witness);
+                let pairs = match commitment_method {
+                    CommitmentMethod::VectorCommitment => {
+                        let scaled = bn254.mul(
+                            [constant(&source, "NEG_G0_X"), constant(&source, "NEG_G0_Y")],
+                            input,
+                        );
+                        let powers_of_h = powers_of_h(&source);
+                        let h_pos = &powers_of_h[(CHUNK_SIZE - pos - 1) * 128..][..128];
+                        let h_last = &powers_of_h[(CHUNK_SIZE - 1) * 128..][..128];
+                        [
+                            words(&commitment),
+                            h_pos.to_vec(),
+                            words(&neg_witness),
+                            words(
+                                &["H_X1", "H_X0", "H_Y1", "H_Y0"]
+                                    .map(|name| constant(&source, name)),
+                            ),
+                            words(&scaled),
+                            h_last.to_vec(),
+                        ]
+                        .concat()
+                    }
+                    _kzg => {
+                        let r = constant(&source, "R");
+                        let mut z = U256::one();
+                        for _ in 0..pos {
+                            z = mul_mod(z, constant(&source, "OMEGA"), r);
+                        }
+                        let g = [constant(&source, "G_X"), constant(&source, "G_Y")];
+                        let minus_input_g = bn254.mul(g, r - input);
+                        let point = bn254.add(commitment, minus_input_g);
+                        let scaled_witness = bn254.mul(witness, z);
+                        let point = bn254.add(point, scaled_witness);
+                        [
+                            words(&point),
+                            words(
+                                &["H_X1", "H_X0", "H_Y1", "H_Y0"]
+                                    .map(|name| constant(&source, name)),
+                            ),
+                            words(&neg_witness),
+                            words(
+                                &["BETA_H_X1", "BETA_H_X0", "BETA_H_Y1", "BETA_H_Y0"]
+                                    .map(|name| constant(&source, name)),
+                            ),
+                        ]
+                        .concat()
+                    }
+                };
+                let pairs: Vec<U256> = pairs.chunks(32).map(U256::from_big_endian).collect();
+                assert_eq!(bn254.pairing(&pairs), expected, "{}", commitment_method);
+            }
+        }
+    }
+}
+
+#[test]
+fn merkle_evm_encoding_is_the_sibling_path() {
+    let fixture = Fixture::new("merkle");
+    let block_commitment = fixture.commit(CommitmentMethod::MerkleKeccak256);
+
+    for tx_order in [0, 77, CHUNK_SIZE + 1] {
+        let proof = fixture.open(CommitmentMethod::MerkleKeccak256, tx_order);
+        let proof = evm::encode_inclusion_proof(&block_commitment, &proof).unwrap();
+        let siblings = abi::decode(
+            &[ParamType::Array(Box::new(ParamType::FixedBytes(32)))],
+            &proof,
+        )
+        .unwrap()
+        .pop()
+        .unwrap()
+        .into_array()
+        .unwrap();
+
+        let raw_tx: &[u8] = fixture.block[tx_order].as_ref();
+        let mut node = keccak256(prefixed(0x00, &keccak256(raw_tx)));
+        for (height, sibling) in siblings.into_iter().enumerate() {
+            let sibling = sibling.into_fixed_bytes().unwrap();
+            node = if (tx_order >> height) & 1 == 0 {
+                keccak256(prefixed(0x01, &[node.as_slice(), &sibling].concat()))
+            } else {
+                keccak256(prefixed(0x01, &[sibling.as_slice(), &node].concat()))
+            };
+        }
+        assert_eq!(node.as_slice(), block_commitment.commitment());
+    }
+}
+
+#[test]
+fn pairing_methods_are_only_verifiable_on_bn254() {
+    let fixture = Fixture::new("curves");
+    for curve in [Curve::Bls12_381, Curve::Bls12_377] {
+        for commitment_method in [CommitmentMethod::VectorCommitment, CommitmentMethod::Kzg] {
+            assert!(evm::solidity_verifier(&fixture.params, curve, commitment_method).is_err());
+        }
+        assert!(
+            evm::solidity_verifier(&fixture.params, curve, CommitmentMethod::MerkleSha256).is_ok()
+        );
+    }
+}
+
+/// The `solc` at `SOLC_PATH`, or else on the `PATH`, if there is one.
+fn solc() -> Option<Solc> {
+    let solc = Solc::default();
+    solc.version().ok().map(|_| solc)
+}
+
+fn compile(solc: &Solc, source: &str, dir: &Path) -> Vec<u8> {
+    let path = dir.join(format!("{}.sol", evm::VERIFIER_CONTRACT));
+    std::fs::write(&path, source).unwrap();
+    let output = solc.compile_source(&path).unwrap();
+    assert!(!output.has_error(), "{:?}", output.errors);
+    output
+        .find(evm::VERIFIER_CONTRACT)
+        .unwrap()
+        .bytecode()
+        .unwrap()
+        .to_vec()
+}
+
+fn verify_inclusion(evm: &mut EVM<InMemoryDB>, verifier: Address, calldata: Vec<u8>) -> bool {
+    let output = call(evm, verifier, calldata);
+    abi::decode(&[ParamType::Bool], &output)
+        .unwrap()
+        .pop()
+        .unwrap()
+        .into_bool()
+        .unwrap()
+}
+
+/// Deploy the generated verifier of every commitment method in revm and check it against
+/// the inclusion proofs of ssal-commitment. Needs `solc` 0.8.19 or later at `SOLC_PATH`
+/// or on the `PATH`, and is skipped without one.
+#[test]
+fn generated_verifier_accepts_exactly_the_valid_proofs() {
+    let Some(solc) = solc() else {
+        eprintln!("Skipped: no solc at SOLC_PATH or on the PATH");
+        return;
+    };
+    let fixture = Fixture::new("solc");
+
+    for commitment_method in [
+        CommitmentMethod::VectorCommitment,
+        CommitmentMethod::Kzg,
+        CommitmentMethod::MerkleKeccak256,
+        CommitmentMethod::MerkleSha256,
+    ] {
+        let source =
+            evm::solidity_verifier(&fixture.params, Curve::Bn254, commitment_method).unwrap();
+        let mut evm = new_evm();
+        let verifier = deploy(&mut evm, compile(&solc, &source, &fixture.srs_dir));
+        let block_commitment = fixture.commit(commitment_method);
+
+        for tx_order in [0, 5, CHUNK_SIZE + 1] {
+            let proof = fixture.open(commitment_method, tx_order);
+            let raw_tx = &fixture.block[tx_order];
+            let calldata = evm::encode_verify_inclusion(&block_commitment, raw_tx, &proof).unwrap();
+            assert!(
+                verify_inclusion(&mut evm, verifier, calldata.to_vec()),
+                "{}",
+                commitment_method,
+            );
+
+            let tampered = RawTransaction::from("tampered");
+            let calldata =
+                evm::encode_verify_inclusion(&block_commitment, &tampered, &proof).unwrap();
+            assert!(!verify_inclusion(&mut evm, verifier, calldata.to_vec()));
+
+            // The block commitment tagged with another curve.
+            let mut other_curve = block_commitment.to_bytes();
+            other_curve[0] = Curve::Bls12_381.tag();
+            let mut calldata = keccak256(evm::VERIFY_INCLUSION)[..4].to_vec();
+            calldata.extend(abi::encode(&[
+                Token::Bytes(other_curve),
+                Token::Bytes(raw_tx.as_ref().to_vec()),
+                Token::Uint(U256::from(tx_order)),
+                Token::Bytes(
+                    evm::encode_inclusion_proof(&block_commitment, &proof)
+                        .unwrap()
+                        .to_vec(),
+                ),
+            ]));
+            assert!(!verify_inclusion(&mut evm, verifier, calldata));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use ark_bn254::Bn254;
+use ark_std::test_rng;
+use ssal_commitment::{
+    error::CommitmentError,
+    param::StructuredReferenceString,
+    srs::{CommitmentParam, CommitmentParamSet},
+    CHUNK_SIZE,
+};
+use ssal_core::{rand, types::Curve};
+
+/// A temporary SRS directory, removed on drop.
+struct SrsDir(PathBuf);
+
+impl SrsDir {
+    fn new(name: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("ssal-srs-{}-{}", name, std::process::id())))
+    }
+
+    fn save(&self, srs: &StructuredReferenceString<Bn254, CHUNK_SIZE>) -> String {
+        CommitmentParam::save(srs, CommitmentParamSet::srs_path(&self.0, Curve::Bn254)).unwrap()
+    }
+}
+
+impl Drop for SrsDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn a_replaced_srs_is_rejected_even_with_a_matching_checksum_file() {
+    let srs_dir = SrsDir::new("replaced");
+    let srs = StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(&mut test_rng());
+    let checksum = srs_dir.save(&srs);
+    let params = CommitmentParamSet::new(&srs_dir.0).with_checksum(Curve::Bn254, &checksum);
+
+    // Another SRS written over the original, together with its own checksum file.
+    let replaced = StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(
+        &mut rand::thread_rng(),
+    );
+    let replaced_checksum = srs_dir.save(&replaced);
+    assert_ne!(checksum, replaced_checksum);
+
+    match params.load(Curve::Bn254) {
+        Err(CommitmentError::ChecksumMismatch {
+            checksum: actual,
+            expected,
+            ..
+        }) => {
+            assert_eq!(actual, replaced_checksum);
+            assert_eq!(expected, checksum);
+        }
+        other => panic!("Expected a checksum mismatch, got {:?}", other),
+    }
+
+    // The original SRS passes the same check.
+    srs_dir.save(&srs);
+    params.load(Curve::Bn254).unwrap();
+}
+
+#[test]
+fn an_srs_without_a_configured_checksum_is_not_loaded() {
+    let srs_dir = SrsDir::new("unconfigured");
+    let srs = StructuredReferenceString::<Bn254, CHUNK_SIZE>::new_srs_for_testing(&mut test_rng());
+    srs_dir.save(&srs);
+
+    let params = CommitmentParamSet::new(&srs_dir.0);
+    assert!(matches!(
+        params.load(Curve::Bn254),
+        Err(CommitmentError::MissingChecksum(Curve::Bn254)),
+    ));
+}
@@ -0,0 +1,231 @@
+use std::str::FromStr;
+
+use ethers_core::types::Address;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{Error, ErrorCode},
+    types::*,
+};
+
+/// The seed from which the leader of a [`SequencerSet`] is derived.
+///
+/// The first seed of a rollup is the hash of its [`RollupId`]. Every seed
+/// after that is derived from the previously closed [`SequencerSet`] (see
+/// [`SequencerSet::next_seed`]) and mixed with the latest block commitment the
+/// SSAL received (see [`ElectionSeed::mix`]), so the seeds of a rollup form a hash
+/// chain that anyone holding the closed sets can recompute but nobody can predict
+/// from the genesis alone.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ElectionSeed([u8; 32]);
+
+impl std::fmt::Display for ElectionSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl AsRef<[u8]> for ElectionSeed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl ElectionSeed {
+    pub fn genesis(rollup_id: &RollupId) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"ssal-election-seed");
+        hasher.update(rollup_id.as_ref().as_bytes());
+        Self(hasher.finalize().into())
+    }
+
+    /// Chain the seed with the outcome of the election it was used for.
+    pub fn next(&self, block_height: &BlockHeight, leader: &SequencerId) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(self.as_ref());
+        hasher.update((block_height.value() as u64).to_be_bytes());
+        hasher.update(leader.as_ref().as_bytes());
+        Self(hasher.finalize().into())
+    }
+
+    /// `sha256(seed || block_commitment)`, with the block commitment encoded as in
+    /// [`BlockCommitment::to_bytes`].
+    pub fn mix(&self, block_commitment: &BlockCommitment) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(self.as_ref());
+        hasher.update(block_commitment.to_bytes());
+        Self(hasher.finalize().into())
+    }
+}
+
+/// A registered sequencer as seen by the election.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Candidate {
+    sequencer_id: SequencerId,
+    /// The wallet address the sequencer registered with, which signs its requests
+    /// and, as the leader, its order commitments.
+    #[serde(default)]
+    address: Address,
+    stake: Stake,
+}
+
+impl Candidate {
+    pub fn new(sequencer_id: SequencerId, address: Address, stake: Stake) -> Self {
+        Self {
+            sequencer_id,
+            address,
+            stake,
+        }
+    }
+
+    pub fn sequencer_id(&self) -> &SequencerId {
+        &self.sequencer_id
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn stake(&self) -> &Stake {
+        &self.stake
+    }
+}
+
+/// The public inputs of an election besides the candidates.
+pub struct ElectionContext<'a> {
+    pub rollup_id: &'a RollupId,
+    pub block_height: &'a BlockHeight,
+    pub seed: &'a ElectionSeed,
+}
+
+impl<'a> ElectionContext<'a> {
+    /// The first 128 bits of `sha256(rollup_id || block_height || seed)` reduced modulo
+    /// `modulus`. Reducing a draw 64 bits wider than the modulus keeps the bias towards
+    /// small results below 2^-64.
+    fn draw(&self, modulus: u64) -> u64 {
+        let mut hasher = Sha256::new();
+        hasher.update(self.rollup_id.as_ref().as_bytes());
+        hasher.update((self.block_height.value() as u64).to_be_bytes());
+        hasher.update(self.seed.as_ref());
+        let digest = hasher.finalize();
+
+        let mut draw_bytes = [0; 16];
+        draw_bytes.copy_from_slice(&digest[..16]);
+        (u128::from_be_bytes(draw_bytes) % modulus as u128) as u64
+    }
+}
+
+pub trait LeaderElection {
+    /// Pick the leader out of `candidates` given in registration order.
+    ///
+    /// Implementations must be deterministic so that anyone holding the closed
+    /// [`SequencerSet`] can recompute the result.
+    fn elect(&self, candidates: &[Candidate], context: &ElectionContext) -> Option<SequencerId>;
+}
+
+/// The IDs of `candidates` sorted by [`SequencerId`], so that the order in which the
+/// sequencers registered does not matter.
+fn sorted_ids(candidates: &[Candidate]) -> Vec<&SequencerId> {
+    let mut sorted: Vec<&SequencerId> = candidates
+        .iter()
+        .map(|candidate| candidate.sequencer_id())
+        .collect();
+    sorted.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    sorted
+}
+
+/// Uniform choice over the candidates sorted by [`SequencerId`].
+pub struct RandomElection;
+
+impl LeaderElection for RandomElection {
+    fn elect(&self, candidates: &[Candidate], context: &ElectionContext) -> Option<SequencerId> {
+        let sorted = sorted_ids(candidates);
+        if sorted.is_empty() {
+            return None;
+        }
+
+        let index = context.draw(sorted.len() as u64) as usize;
+        Some(sorted[index].clone())
+    }
+}
+
+/// Rotates over the candidates sorted by [`SequencerId`] with the block height.
+pub struct RoundRobinElection;
+
+impl LeaderElection for RoundRobinElection {
+    fn elect(&self, candidates: &[Candidate], context: &ElectionContext) -> Option<SequencerId> {
+        let sorted = sorted_ids(candidates);
+        if sorted.is_empty() {
+            return None;
+        }
+
+        let index = context.block_height.value() % sorted.len();
+        Some(sorted[index].clone())
+    }
+}
+
+/// Picks a candidate with probability proportional to its stake. Candidates
+/// with zero stake are never elected.
+pub struct StakeWeightedElection;
+
+impl LeaderElection for StakeWeightedElection {
+    fn elect(&self, candidates: &[Candidate], context: &ElectionContext) -> Option<SequencerId> {
+        let total_stake = candidates.iter().try_fold(0u64, |sum, candidate| {
+            sum.checked_add(candidate.stake().value())
+        })?;
+        if total_stake == 0 {
+            return None;
+        }
+
+        let mut draw = context.draw(total_stake);
+        for candidate in candidates {
+            if draw < candidate.stake().value() {
+                return Some(candidate.sequencer_id().clone());
+            }
+            draw -= candidate.stake().value();
+        }
+        None
+    }
+}
+
+/// The election policy of a rollup, chosen at registration.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum ElectionMethod {
+    /// See [`RandomElection`].
+    #[default]
+    Random,
+    /// See [`RoundRobinElection`].
+    RoundRobin,
+    /// See [`StakeWeightedElection`].
+    StakeWeighted,
+}
+
+impl FromStr for ElectionMethod {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "random" => Ok(Self::Random),
+            "round-robin" => Ok(Self::RoundRobin),
+            "stake-weighted" => Ok(Self::StakeWeighted),
+            _others => Err(Error::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown election method: {:?}", value),
+            )),
+        }
+    }
+}
+
+impl ElectionMethod {
+    pub fn election(&self) -> &'static dyn LeaderElection {
+        match self {
+            Self::Random => &RandomElection,
+            Self::RoundRobin => &RoundRobinElection,
+            Self::StakeWeighted => &StakeWeightedElection,
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use std::any::Any;
+
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
+    Json,
 };
+use serde::{Deserialize, Serialize};
 
 pub trait WrapError {
     type Output;
@@ -9,11 +13,16 @@ pub trait WrapError {
     fn wrap<C>(self, context: C) -> Self::Output
     where
         C: std::fmt::Debug + 'static;
+
+    /// Same as [`WrapError::wrap`], but tags the error with `code`.
+    fn wrap_as<C>(self, code: ErrorCode, context: C) -> Self::Output
+    where
+        C: std::fmt::Debug + 'static;
 }
 
 impl<T, E> WrapError for Result<T, E>
 where
-    E: std::error::Error + 'static,
+    E: std::error::Error + Send + Sync + 'static,
 {
     type Output = Result<T, Error>;
 
@@ -23,6 +32,13 @@ where
     {
         self.map_err(|error| Error::boxed_error(context, error))
     }
+
+    fn wrap_as<C>(self, code: ErrorCode, context: C) -> Self::Output
+    where
+        C: std::fmt::Debug + 'static,
+    {
+        self.map_err(|error| Error::boxed_error(context, error).with_code(code))
+    }
 }
 
 impl<T> WrapError for Option<T> {
@@ -32,12 +48,78 @@ impl<T> WrapError for Option<T> {
     where
         C: std::fmt::Debug + 'static,
     {
-        self.ok_or(Error::none_type(context))
+        self.ok_or_else(|| Error::none_type(context))
     }
+
+    fn wrap_as<C>(self, code: ErrorCode, context: C) -> Self::Output
+    where
+        C: std::fmt::Debug + 'static,
+    {
+        self.ok_or_else(|| Error::none_type(context).with_code(code))
+    }
+}
+
+/// The machine-readable kind of an [`Error`], shared by the servers and their clients.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum ErrorCode {
+    Internal,
+    InvalidRequest,
+    Unauthorized,
+    NotFound,
+    RollupNotFound,
+    RollupAlreadyRegistered,
+    RollupNotOperational,
+    InvalidStateTransition,
+    SequencerNotRegistered,
+    SequencerAlreadyRegistered,
+    SequencerExcluded,
+    RegistrationInProgress,
+    ElectionFailed,
+    NotLeader,
+    /// Fewer followers than the durability mode of the rollup requires acknowledged
+    /// the transaction in time. The transaction stays sequenced and keeps being synced.
+    QuorumNotReached,
+    InvalidSignature,
+    StorageFailure,
+    UpstreamFailure,
+}
+
+impl ErrorCode {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::InvalidRequest => StatusCode::BAD_REQUEST,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::RollupNotFound => StatusCode::NOT_FOUND,
+            Self::RollupAlreadyRegistered => StatusCode::CONFLICT,
+            Self::RollupNotOperational => StatusCode::CONFLICT,
+            Self::InvalidStateTransition => StatusCode::CONFLICT,
+            Self::SequencerNotRegistered => StatusCode::NOT_FOUND,
+            Self::SequencerAlreadyRegistered => StatusCode::CONFLICT,
+            Self::SequencerExcluded => StatusCode::FORBIDDEN,
+            Self::RegistrationInProgress => StatusCode::SERVICE_UNAVAILABLE,
+            Self::ElectionFailed => StatusCode::CONFLICT,
+            Self::NotLeader => StatusCode::MISDIRECTED_REQUEST,
+            Self::QuorumNotReached => StatusCode::SERVICE_UNAVAILABLE,
+            Self::InvalidSignature => StatusCode::BAD_REQUEST,
+            Self::StorageFailure => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::UpstreamFailure => StatusCode::BAD_GATEWAY,
+        }
+    }
+}
+
+/// The JSON body of an error response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    pub message: String,
+    pub context: Option<String>,
 }
 
 pub struct Error {
-    context: Box<dyn std::fmt::Debug>,
+    code: ErrorCode,
+    context: String,
     source: ErrorKind,
 }
 
@@ -50,11 +132,18 @@ impl std::fmt::Debug for Error {
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.source {
-            ErrorKind::Boxed(source) => write!(f, "{:?} ({})", self.context, source),
-            ErrorKind::PlainString => write!(f, "{:?}", self.context),
-            ErrorKind::NoneType => write!(f, "{} ({:?})", self.source, self.context),
+            ErrorKind::Boxed(source) => {
+                write!(f, "[{:?}] {} ({})", self.code, self.context, source)
+            }
+            ErrorKind::PlainString => write!(f, "[{:?}] {}", self.code, self.context),
+            ErrorKind::NoneType => {
+                write!(f, "[{:?}] {} ({})", self.code, self.source, self.context)
+            }
+            ErrorKind::Remote(message) => match self.context.is_empty() {
+                true => write!(f, "[{:?}] {}", self.code, message),
+                false => write!(f, "[{:?}] {} ({})", self.code, message, self.context),
+            },
         }
-        // write!(f, "{:?} ({})", self.context, self.source)
     }
 }
 
@@ -62,62 +151,137 @@ impl std::error::Error for Error {}
 
 impl From<&str> for Error {
     fn from(value: &str) -> Self {
-        Self {
-            context: Box::new(value.to_string()),
-            source: ErrorKind::PlainString,
-        }
+        Self::new(ErrorCode::Internal, value)
     }
 }
 
 impl From<String> for Error {
     fn from(value: String) -> Self {
+        Self::new(ErrorCode::Internal, value)
+    }
+}
+
+impl From<ErrorBody> for Error {
+    fn from(value: ErrorBody) -> Self {
         Self {
-            context: Box::new(value),
-            source: ErrorKind::PlainString,
+            code: value.code,
+            context: value.context.unwrap_or_default(),
+            source: ErrorKind::Remote(value.message),
         }
     }
 }
 
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+        (self.code.status_code(), Json(self.body())).into_response()
     }
 }
 
 impl Error {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            context: message.into(),
+            source: ErrorKind::PlainString,
+        }
+    }
+
     pub fn boxed_error<C, E>(context: C, source: E) -> Self
     where
         C: std::fmt::Debug + 'static,
-        E: std::error::Error + 'static,
+        E: std::error::Error + Send + Sync + 'static,
     {
         Self {
-            context: Box::new(context),
+            code: ErrorCode::Internal,
+            context: describe(context),
             source: ErrorKind::Boxed(Box::new(source)),
         }
     }
 
+    /// An unexpected `None`. Where it means that a requested resource is missing, tag
+    /// it with [`ErrorCode::NotFound`] through [`WrapError::wrap_as`].
     pub fn none_type<C>(context: C) -> Self
     where
         C: std::fmt::Debug + 'static,
     {
         Self {
-            context: Box::new(context),
+            code: ErrorCode::Internal,
+            context: describe(context),
             source: ErrorKind::NoneType,
         }
     }
 
+    /// Rebuild the error from an error response of the SSAL or a sequencer.
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        match response.text().await {
+            Ok(text) => match serde_json::from_str::<ErrorBody>(&text) {
+                Ok(body) => body.into(),
+                Err(_) => Self::new(
+                    ErrorCode::UpstreamFailure,
+                    format!("Unexpected error response ({}): {}", status, text),
+                ),
+            },
+            Err(error) => Self::boxed_error("Failed to read the error response", error)
+                .with_code(ErrorCode::UpstreamFailure),
+        }
+    }
+
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
     pub fn is_none_type(&self) -> bool {
         match &self.source {
             ErrorKind::NoneType => true,
             _others => false,
         }
     }
+
+    pub fn body(&self) -> ErrorBody {
+        let (message, context) = match &self.source {
+            ErrorKind::Boxed(source) => (source.to_string(), Some(self.context.clone())),
+            ErrorKind::PlainString => (self.context.clone(), None),
+            ErrorKind::NoneType => (self.source.to_string(), Some(self.context.clone())),
+            ErrorKind::Remote(message) => (
+                message.clone(),
+                Some(self.context.clone()).filter(|context| !context.is_empty()),
+            ),
+        };
+        ErrorBody {
+            code: self.code,
+            message,
+            context,
+        }
+    }
+}
+
+/// Strings are kept as they are and everything else is formatted with `Debug`.
+fn describe<C>(context: C) -> String
+where
+    C: std::fmt::Debug + 'static,
+{
+    let any_context = &context as &dyn Any;
+    if let Some(context) = any_context.downcast_ref::<String>() {
+        context.clone()
+    } else if let Some(context) = any_context.downcast_ref::<&str>() {
+        context.to_string()
+    } else {
+        format!("{:?}", context)
+    }
 }
 
 pub enum ErrorKind {
-    Boxed(Box<dyn std::error::Error>),
+    Boxed(Box<dyn std::error::Error + Send + Sync>),
     PlainString,
     NoneType,
+    /// An error received in an [`ErrorBody`] from another server.
+    Remote(String),
 }
 
 impl std::fmt::Display for ErrorKind {
@@ -126,6 +290,7 @@ impl std::fmt::Display for ErrorKind {
             Self::Boxed(error) => write!(f, "{}", error),
             Self::PlainString => write!(f, ""),
             Self::NoneType => write!(f, "The value returned None"),
+            Self::Remote(message) => write!(f, "{}", message),
         }
     }
 }
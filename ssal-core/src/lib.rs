@@ -1,6 +1,8 @@
+pub mod election;
 pub mod error;
 pub use axum;
 pub use bincode;
+pub use ethers_core;
 pub use rand;
 pub use reqwest;
 pub use rocksdb;
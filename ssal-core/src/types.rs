@@ -1,9 +1,17 @@
-use std::collections::{hash_set::Iter, HashSet};
+use std::collections::{HashMap, HashSet};
 
-use rand::{self, seq::SliceRandom};
+use ethers_core::{
+    abi::{self, Token},
+    types::{Address, Bytes, Signature, U256},
+    utils::keccak256,
+};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use crate::error::Error;
+use crate::{
+    election::*,
+    error::{Error, ErrorCode, WrapError},
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BlockHeight(usize);
@@ -99,25 +107,109 @@ impl From<&String> for RollupId {
     }
 }
 
+/// The lifecycle of a rollup:
+///
+/// `Registered` -> `Active` <-> `Paused`, and any state but `Retired` -> `Retired`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum RollupState {
+    /// Registered, but no block has been closed yet.
+    Registered,
+    /// At least one block has been closed.
+    Active,
+    /// Neither blocks nor sequencer registrations are accepted until resumed.
+    Paused,
+    /// Deregistered for good. The rollup ID cannot be registered again.
+    Retired,
+}
+
+impl RollupState {
+    /// Whether the rollup accepts `CloseBlock` and `RegisterSequencer`.
+    pub fn is_operational(&self) -> bool {
+        matches!(self, Self::Registered | Self::Active)
+    }
+
+    fn can_transition_to(&self, next: &Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::Registered, Self::Active)
+                | (Self::Active, Self::Paused)
+                | (Self::Paused, Self::Active)
+                | (Self::Registered, Self::Retired)
+                | (Self::Active, Self::Retired)
+                | (Self::Paused, Self::Retired)
+        )
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct RollupSet(HashSet<RollupId>);
+pub struct RollupSet(HashMap<RollupId, RollupState>);
 
 impl RollupSet {
     pub fn register(&mut self, rollup_id: RollupId) -> Result<(), Error> {
-        match self.0.insert(rollup_id) {
-            true => Ok(()),
-            false => Err(Error::from("Rollup already exists.")),
+        match self.0.contains_key(&rollup_id) {
+            false => {
+                self.0.insert(rollup_id, RollupState::Registered);
+                Ok(())
+            }
+            true => Err(Error::new(
+                ErrorCode::RollupAlreadyRegistered,
+                "Rollup already exists.",
+            )),
         }
     }
 
     pub fn contains(&self, rollup_id: &RollupId) -> bool {
-        self.0.contains(rollup_id)
+        self.0.contains_key(rollup_id)
     }
 
-    pub fn deregister(&mut self, rollup_id: &RollupId) -> Result<(), Error> {
-        match self.0.remove(rollup_id) {
+    pub fn state(&self, rollup_id: &RollupId) -> Result<RollupState, Error> {
+        self.0.get(rollup_id).copied().wrap_as(
+            ErrorCode::RollupNotFound,
+            format!("Rollup is not registered: {:?}", rollup_id),
+        )
+    }
+
+    /// Fails unless the rollup accepts blocks and sequencer registrations.
+    pub fn check_operational(&self, rollup_id: &RollupId) -> Result<(), Error> {
+        let state = self.state(rollup_id)?;
+        match state.is_operational() {
             true => Ok(()),
-            false => Err(Error::from("Rollup already removed.")),
+            false => Err(Error::new(
+                ErrorCode::RollupNotOperational,
+                format!("Rollup {:?} is {:?}.", rollup_id, state),
+            )),
+        }
+    }
+
+    pub fn transition(&mut self, rollup_id: &RollupId, next: RollupState) -> Result<(), Error> {
+        let state = self.0.get_mut(rollup_id).wrap_as(
+            ErrorCode::RollupNotFound,
+            format!("Rollup is not registered: {:?}", rollup_id),
+        )?;
+        match state.can_transition_to(&next) {
+            true => {
+                *state = next;
+                Ok(())
+            }
+            false => Err(Error::new(
+                ErrorCode::InvalidStateTransition,
+                format!(
+                    "Rollup {:?} cannot transition from {:?} to {:?}.",
+                    rollup_id, state, next,
+                ),
+            )),
+        }
+    }
+
+    /// Retire the rollup. The entry is kept so that its history stays queryable
+    /// and the ID cannot be reused.
+    pub fn deregister(&mut self, rollup_id: &RollupId) -> Result<(), Error> {
+        match self.state(rollup_id)? {
+            RollupState::Retired => Err(Error::new(
+                ErrorCode::InvalidStateTransition,
+                "Rollup already removed.",
+            )),
+            _others => self.transition(rollup_id, RollupState::Retired),
         }
     }
 }
@@ -155,49 +247,742 @@ impl From<&String> for SequencerId {
     }
 }
 
+/// Per-rollup settings given at `RegisterRollup` and kept by the SSAL server.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RollupConfig {
+    #[serde(default)]
+    election_method: ElectionMethod,
+    #[serde(default)]
+    commitment_method: CommitmentMethod,
+    #[serde(default)]
+    curve: Curve,
+    #[serde(default)]
+    durability_mode: DurabilityMode,
+}
+
+impl RollupConfig {
+    pub fn new(
+        election_method: ElectionMethod,
+        commitment_method: CommitmentMethod,
+        curve: Curve,
+        durability_mode: DurabilityMode,
+    ) -> Self {
+        Self {
+            election_method,
+            commitment_method,
+            curve,
+            durability_mode,
+        }
+    }
+
+    pub fn election_method(&self) -> ElectionMethod {
+        self.election_method
+    }
+
+    pub fn commitment_method(&self) -> CommitmentMethod {
+        self.commitment_method
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    pub fn durability_mode(&self) -> DurabilityMode {
+        self.durability_mode
+    }
+}
+
+/// The scheme sequencers commit to the blocks of a rollup with, chosen at registration.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum CommitmentMethod {
+    /// Vector commitment over chunks of the block.
+    #[default]
+    VectorCommitment,
+    /// KZG polynomial commitment over chunks of the block.
+    Kzg,
+    /// Binary Merkle tree hashed with keccak256. Verifiable without pairings.
+    MerkleKeccak256,
+    /// Binary Merkle tree hashed with sha256. Verifiable without pairings.
+    MerkleSha256,
+}
+
+impl std::fmt::Display for CommitmentMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VectorCommitment => write!(f, "vector-commitment"),
+            Self::Kzg => write!(f, "kzg"),
+            Self::MerkleKeccak256 => write!(f, "merkle-keccak256"),
+            Self::MerkleSha256 => write!(f, "merkle-sha256"),
+        }
+    }
+}
+
+impl std::str::FromStr for CommitmentMethod {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "vector-commitment" => Ok(Self::VectorCommitment),
+            "kzg" => Ok(Self::Kzg),
+            "merkle-keccak256" => Ok(Self::MerkleKeccak256),
+            "merkle-sha256" => Ok(Self::MerkleSha256),
+            _others => Err(Error::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown commitment method: {:?}", value),
+            )),
+        }
+    }
+}
+
+impl CommitmentMethod {
+    /// The byte identifying the method in a [`BlockCommitment`].
+    pub fn tag(&self) -> u8 {
+        match self {
+            Self::VectorCommitment => 0,
+            Self::Kzg => 1,
+            Self::MerkleKeccak256 => 2,
+            Self::MerkleSha256 => 3,
+        }
+    }
+
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::VectorCommitment),
+            1 => Some(Self::Kzg),
+            2 => Some(Self::MerkleKeccak256),
+            3 => Some(Self::MerkleSha256),
+            _others => None,
+        }
+    }
+}
+
+/// The pairing curve of the vector and KZG commitments of a rollup, chosen at
+/// registration. Merkle trees do not use it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Curve {
+    #[default]
+    Bn254,
+    Bls12_381,
+    Bls12_377,
+}
+
+impl std::fmt::Display for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bn254 => write!(f, "bn254"),
+            Self::Bls12_381 => write!(f, "bls12-381"),
+            Self::Bls12_377 => write!(f, "bls12-377"),
+        }
+    }
+}
+
+impl std::str::FromStr for Curve {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "bn254" => Ok(Self::Bn254),
+            "bls12-381" => Ok(Self::Bls12_381),
+            "bls12-377" => Ok(Self::Bls12_377),
+            _others => Err(Error::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown curve: {:?}", value),
+            )),
+        }
+    }
+}
+
+impl Curve {
+    pub const ALL: [Self; 3] = [Self::Bn254, Self::Bls12_381, Self::Bls12_377];
+
+    /// The byte identifying the curve in a [`BlockCommitment`].
+    pub fn tag(&self) -> u8 {
+        match self {
+            Self::Bn254 => 0,
+            Self::Bls12_381 => 1,
+            Self::Bls12_377 => 2,
+        }
+    }
+
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Bn254),
+            1 => Some(Self::Bls12_381),
+            2 => Some(Self::Bls12_377),
+            _others => None,
+        }
+    }
+}
+
+/// How many members of the [`SequencerSet`] must store a transaction before the leader
+/// returns its order commitment, chosen at registration.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum DurabilityMode {
+    /// The leader answers as soon as it stored the transaction.
+    #[default]
+    LeaderOnly,
+    /// A majority of the sequencer set, the leader included.
+    Majority,
+    /// Every follower.
+    All,
+}
+
+impl std::fmt::Display for DurabilityMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LeaderOnly => write!(f, "leader-only"),
+            Self::Majority => write!(f, "majority"),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
+impl std::str::FromStr for DurabilityMode {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "leader-only" => Ok(Self::LeaderOnly),
+            "majority" => Ok(Self::Majority),
+            "all" => Ok(Self::All),
+            _others => Err(Error::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown durability mode: {:?}", value),
+            )),
+        }
+    }
+}
+
+impl DurabilityMode {
+    /// The number of followers which must acknowledge a transaction in a sequencer set
+    /// of `set_size` members, the leader included.
+    pub fn required_acks(&self, set_size: usize) -> usize {
+        match self {
+            Self::LeaderOnly => 0,
+            Self::Majority => set_size / 2,
+            Self::All => set_size.saturating_sub(1),
+        }
+    }
+}
+
+/// A block commitment tagged with the curve and the method it was computed with, so
+/// that verifiers know how to decode the chunk commitments of its inclusion proofs.
+/// It is encoded as follows, both on chain and by `/get-block-commitment`:
+///
+/// | Byte | Content                     |
+/// |------|-----------------------------|
+/// | 0    | [`Curve::tag`]              |
+/// | 1    | [`CommitmentMethod::tag`]   |
+/// | 2..  | the commitment              |
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockCommitment {
+    curve: Curve,
+    commitment_method: CommitmentMethod,
+    commitment: Bytes,
+}
+
+impl BlockCommitment {
+    pub fn new(curve: Curve, commitment_method: CommitmentMethod, commitment: Bytes) -> Self {
+        Self {
+            curve,
+            commitment_method,
+            commitment,
+        }
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    pub fn commitment_method(&self) -> CommitmentMethod {
+        self.commitment_method
+    }
+
+    pub fn commitment(&self) -> &Bytes {
+        &self.commitment
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + self.commitment.len());
+        bytes.push(self.curve.tag());
+        bytes.push(self.commitment_method.tag());
+        bytes.extend_from_slice(&self.commitment);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (curve, commitment_method, commitment) = match bytes {
+            [curve, commitment_method, commitment @ ..] => (curve, commitment_method, commitment),
+            _others => {
+                return Err(Error::new(
+                    ErrorCode::InvalidRequest,
+                    "The block commitment is missing its tags",
+                ))
+            }
+        };
+        let curve = Curve::from_tag(*curve).wrap_as(
+            ErrorCode::InvalidRequest,
+            format!("Unknown curve tag: {}", curve),
+        )?;
+        let commitment_method = CommitmentMethod::from_tag(*commitment_method).wrap_as(
+            ErrorCode::InvalidRequest,
+            format!("Unknown commitment method tag: {}", commitment_method),
+        )?;
+        Ok(Self::new(
+            curve,
+            commitment_method,
+            commitment.to_vec().into(),
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Stake(u64);
+
+impl std::fmt::Display for Stake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for Stake {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl Stake {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SequencerSet {
     block_height: BlockHeight,
-    set: HashSet<SequencerId>,
+    /// Registered sequencers in registration order.
+    set: Vec<Candidate>,
     leader: Option<SequencerId>,
+    seed: Option<ElectionSeed>,
+    /// The block commitment mixed into `seed`, if any.
+    #[serde(default)]
+    block_commitment: Option<BlockCommitment>,
+    election_method: ElectionMethod,
+    /// Leaders replaced by a failover, in the order they were elected.
+    failed_leaders: Vec<SequencerId>,
 }
 
 impl SequencerSet {
     pub fn new(block_height: BlockHeight) -> Self {
         Self {
             block_height,
-            set: HashSet::default(),
+            set: Vec::default(),
             leader: None,
+            seed: None,
+            block_commitment: None,
+            election_method: ElectionMethod::default(),
+            failed_leaders: Vec::default(),
         }
     }
 
-    pub fn register(&mut self, sequencer_id: SequencerId) -> Result<(), Error> {
-        match self.set.insert(sequencer_id) {
-            true => Ok(()),
-            false => Err(Error::from("Sequencer is already registered")),
+    pub fn register(
+        &mut self,
+        sequencer_id: SequencerId,
+        address: Address,
+        stake: Stake,
+    ) -> Result<(), Error> {
+        match self.contains(&sequencer_id) {
+            false => {
+                self.set.push(Candidate::new(sequencer_id, address, stake));
+                Ok(())
+            }
+            true => Err(Error::new(
+                ErrorCode::SequencerAlreadyRegistered,
+                "Sequencer is already registered",
+            )),
         }
     }
 
-    pub fn elect_leader(&mut self) -> Result<SequencerId, Error> {
-        let sequencer_vec: Vec<SequencerId> = self.set.iter().cloned().collect();
-        match sequencer_vec.choose(&mut rand::thread_rng()) {
-            Some(leader) => {
-                self.leader = Some(leader.clone());
-                Ok(leader.clone())
+    pub fn deregister(&mut self, sequencer_id: &SequencerId) -> Result<(), Error> {
+        match self.contains(sequencer_id) {
+            true => {
+                self.set
+                    .retain(|candidate| candidate.sequencer_id() != sequencer_id);
+                Ok(())
             }
-            None => Err(Error::from("Failed to elect the leader.")),
+            false => Err(Error::new(
+                ErrorCode::SequencerNotRegistered,
+                "Sequencer is not registered",
+            )),
         }
     }
 
+    /// Remove every sequencer that is excluded at the block height of the set.
+    pub fn remove_excluded(&mut self, exclusion_list: &ExclusionList) {
+        let block_height = self.block_height.clone();
+        self.set.retain(|candidate| {
+            !exclusion_list.is_excluded(candidate.sequencer_id(), &block_height)
+        });
+    }
+
+    pub fn contains(&self, sequencer_id: &SequencerId) -> bool {
+        self.iter().any(|registered| registered == sequencer_id)
+    }
+
+    /// The wallet address `sequencer_id` registered with.
+    pub fn address(&self, sequencer_id: &SequencerId) -> Option<&Address> {
+        self.set
+            .iter()
+            .find(|candidate| candidate.sequencer_id() == sequencer_id)
+            .map(|candidate| candidate.address())
+    }
+
+    /// Whether `address` is the wallet address of the leader, or of a leader replaced by
+    /// a failover, whose order commitments stay part of the block.
+    pub fn is_leader_address(&self, address: &Address) -> bool {
+        self.failed_leaders
+            .iter()
+            .chain(self.leader.as_ref())
+            .any(|leader_id| self.address(leader_id) == Some(address))
+    }
+
+    /// Check that `signature` over `digest` was made with the wallet key `sequencer_id`
+    /// registered with.
+    pub fn authenticate(
+        &self,
+        sequencer_id: &SequencerId,
+        digest: [u8; 32],
+        signature: &Signature,
+    ) -> Result<(), Error> {
+        let address = self.address(sequencer_id).wrap_as(
+            ErrorCode::SequencerNotRegistered,
+            format!("{:?} is not in the sequencer set", sequencer_id),
+        )?;
+        signature.verify(digest.to_vec(), *address).wrap_as(
+            ErrorCode::InvalidSignature,
+            format!("The request was not signed by {:?}", sequencer_id),
+        )
+    }
+
+    /// Elect the leader deterministically from `seed`, mixed with `block_commitment`
+    /// if given, with `election_method` and record all of them in the set so that the
+    /// election can be checked with [`SequencerSet::verify_seed`] and
+    /// [`SequencerSet::verify_leader`].
+    pub fn elect_leader(
+        &mut self,
+        rollup_id: &RollupId,
+        seed: ElectionSeed,
+        block_commitment: Option<BlockCommitment>,
+        election_method: ElectionMethod,
+    ) -> Result<SequencerId, Error> {
+        let seed = match &block_commitment {
+            Some(block_commitment) => seed.mix(block_commitment),
+            None => seed,
+        };
+        let leader = self
+            .replay_election(rollup_id, &seed, election_method, &[])
+            .wrap_as(ErrorCode::ElectionFailed, "Failed to elect the leader.")?;
+        self.leader = Some(leader.clone());
+        self.seed = Some(seed);
+        self.block_commitment = block_commitment;
+        self.election_method = election_method;
+        self.failed_leaders.clear();
+        Ok(leader)
+    }
+
+    /// Replace the current leader after a quorum of followers reported it
+    /// unreachable. The replacement is elected among the remaining sequencers
+    /// with the seed chained over every failed leader, so it stays verifiable.
+    pub fn fail_over(&mut self, rollup_id: &RollupId) -> Result<SequencerId, Error> {
+        let failed_leader = self
+            .leader
+            .clone()
+            .wrap_as(ErrorCode::ElectionFailed, "The leader is not elected.")?;
+        let seed = self
+            .seed
+            .clone()
+            .wrap_as(ErrorCode::ElectionFailed, "The leader is not elected.")?;
+
+        let mut failed_leaders = self.failed_leaders.clone();
+        failed_leaders.push(failed_leader);
+        let leader = self
+            .replay_election(rollup_id, &seed, self.election_method, &failed_leaders)
+            .wrap_as(
+                ErrorCode::ElectionFailed,
+                "Failed to elect a replacement leader.",
+            )?;
+        self.leader = Some(leader.clone());
+        self.failed_leaders = failed_leaders;
+        Ok(leader)
+    }
+
+    /// The number of failure reports needed to replace the leader: a majority of
+    /// the followers that have not failed as a leader themselves, and never fewer than
+    /// two. A single follower cannot depose the leader, so sets that are left with
+    /// fewer than two such followers do not fail over.
+    pub fn failover_quorum(&self) -> usize {
+        let followers = self.set.len().saturating_sub(self.failed_leaders.len() + 1);
+        (followers / 2 + 1).max(2)
+    }
+
+    /// Recompute the election and every failover from the stored seed and
+    /// method and check that they yield the stored leaders.
+    pub fn verify_leader(&self, rollup_id: &RollupId) -> bool {
+        let (leader, seed) = match (&self.leader, &self.seed) {
+            (Some(leader), Some(seed)) => (leader, seed),
+            _others => return false,
+        };
+
+        let elected_leaders = self.failed_leaders.iter().chain(Some(leader));
+        elected_leaders.enumerate().all(|(round, elected_leader)| {
+            self.replay_election(
+                rollup_id,
+                seed,
+                self.election_method,
+                &self.failed_leaders[..round],
+            )
+            .as_ref()
+                == Some(elected_leader)
+        })
+    }
+
+    /// Check that the stored seed follows from the closed set of the previous block
+    /// height, or from the genesis seed for the first one, and the stored block
+    /// commitment.
+    pub fn verify_seed(&self, rollup_id: &RollupId, previous: Option<&SequencerSet>) -> bool {
+        let seed = match previous {
+            Some(previous) => match previous.next_seed() {
+                Some(seed) => seed,
+                None => return false,
+            },
+            None => ElectionSeed::genesis(rollup_id),
+        };
+        let seed = match &self.block_commitment {
+            Some(block_commitment) => seed.mix(block_commitment),
+            None => seed,
+        };
+        self.seed.as_ref() == Some(&seed)
+    }
+
+    /// Derive the seed for the next election of the rollup from this closed set.
+    /// Failovers do not affect the chain: the originally elected leader is used.
+    pub fn next_seed(&self) -> Option<ElectionSeed> {
+        let seed = self.seed.as_ref()?;
+        let leader = self.failed_leaders.first().or(self.leader.as_ref())?;
+        Some(seed.next(&self.block_height, leader))
+    }
+
+    fn replay_election(
+        &self,
+        rollup_id: &RollupId,
+        seed: &ElectionSeed,
+        election_method: ElectionMethod,
+        failed_leaders: &[SequencerId],
+    ) -> Option<SequencerId> {
+        let mut candidates = self.set.clone();
+        let mut seed = seed.clone();
+        for failed_leader in failed_leaders {
+            candidates.retain(|candidate| candidate.sequencer_id() != failed_leader);
+            seed = seed.next(&self.block_height, failed_leader);
+        }
+
+        let context = ElectionContext {
+            rollup_id,
+            block_height: &self.block_height,
+            seed: &seed,
+        };
+        election_method.election().elect(&candidates, &context)
+    }
+
+    pub fn block_height(&self) -> BlockHeight {
+        self.block_height.clone()
+    }
+
     pub fn leader(&self) -> Option<SequencerId> {
-        match &self.leader {
-            Some(leader) => Some(leader.clone()),
-            None => None,
+        self.leader.clone()
+    }
+
+    pub fn seed(&self) -> Option<ElectionSeed> {
+        self.seed.clone()
+    }
+
+    pub fn block_commitment(&self) -> Option<&BlockCommitment> {
+        self.block_commitment.as_ref()
+    }
+
+    pub fn election_method(&self) -> ElectionMethod {
+        self.election_method
+    }
+
+    pub fn failed_leaders(&self) -> &[SequencerId] {
+        &self.failed_leaders
+    }
+
+    pub fn candidates(&self) -> &[Candidate] {
+        &self.set
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SequencerId> {
+        self.set.iter().map(|candidate| candidate.sequencer_id())
+    }
+}
+
+/// The followers which reported the leader of a closed [`SequencerSet`] unreachable.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeaderFailureReport {
+    leader_id: SequencerId,
+    reporters: HashSet<SequencerId>,
+}
+
+impl LeaderFailureReport {
+    pub fn new(leader_id: SequencerId) -> Self {
+        Self {
+            leader_id,
+            reporters: HashSet::default(),
+        }
+    }
+
+    pub fn leader_id(&self) -> &SequencerId {
+        &self.leader_id
+    }
+
+    /// Returns the number of distinct reporters so far.
+    pub fn report(&mut self, reporter_id: SequencerId) -> usize {
+        self.reporters.insert(reporter_id);
+        self.reporters.len()
+    }
+}
+
+/// A block commitment submitted for a closed [`SequencerSet`], with the signatures of the
+/// sequencers which submitted it. Every sequencer builds the same block, so the followers
+/// can stand in for a leader which withholds the commitment.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EndorsedBlockCommitment {
+    block_commitment: BlockCommitment,
+    endorsements: Vec<(SequencerId, Signature)>,
+}
+
+impl EndorsedBlockCommitment {
+    pub fn new(block_commitment: BlockCommitment) -> Self {
+        Self {
+            block_commitment,
+            endorsements: Vec::default(),
         }
     }
 
-    pub fn iter<'a>(&'a self) -> Iter<'a, SequencerId> {
-        self.set.iter()
+    pub fn block_commitment(&self) -> &BlockCommitment {
+        &self.block_commitment
+    }
+
+    pub fn is_endorsed_by(&self, sequencer_id: &SequencerId) -> bool {
+        self.endorsements
+            .iter()
+            .any(|(endorser_id, _signature)| endorser_id == sequencer_id)
+    }
+
+    pub fn endorse(&mut self, sequencer_id: SequencerId, signature: Signature) {
+        if !self.is_endorsed_by(&sequencer_id) {
+            self.endorsements.push((sequencer_id, signature));
+        }
+    }
+
+    /// Whether the current leader of `sequencer_set`, or a failover quorum of its
+    /// followers, signed `digest` over the commitment. Failed leaders do not count.
+    pub fn is_final(&self, sequencer_set: &SequencerSet, digest: [u8; 32]) -> bool {
+        let leader_id = sequencer_set.leader();
+        let mut follower_count = 0;
+        for (endorser_id, signature) in self.endorsements.iter() {
+            if sequencer_set.failed_leaders().contains(endorser_id)
+                || sequencer_set
+                    .authenticate(endorser_id, digest, signature)
+                    .is_err()
+            {
+                continue;
+            }
+            match leader_id.as_ref() == Some(endorser_id) {
+                true => return true,
+                false => follower_count += 1,
+            }
+        }
+        follower_count >= sequencer_set.failover_quorum()
+    }
+}
+
+/// Why and for how long a sequencer is kept out of the sequencer sets of a rollup.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Exclusion {
+    reason: String,
+    from: BlockHeight,
+    /// Exclusive upper bound. `None` means the exclusion is permanent.
+    until: Option<BlockHeight>,
+}
+
+impl Exclusion {
+    pub fn new(reason: String, from: BlockHeight, until: Option<BlockHeight>) -> Self {
+        Self {
+            reason,
+            from,
+            until,
+        }
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn from(&self) -> &BlockHeight {
+        &self.from
+    }
+
+    pub fn until(&self) -> Option<&BlockHeight> {
+        self.until.as_ref()
+    }
+
+    pub fn is_permanent(&self) -> bool {
+        self.until.is_none()
+    }
+
+    pub fn is_active(&self, block_height: &BlockHeight) -> bool {
+        match &self.until {
+            Some(until) => block_height.value() < until.value(),
+            None => true,
+        }
+    }
+}
+
+/// Every exclusion of the sequencers of a rollup, in the order they were made. A new
+/// exclusion never lifts an earlier one: a sequencer stays excluded while any of its
+/// exclusions is active, so a permanent exclusion is never downgraded.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ExclusionList(HashMap<SequencerId, Vec<Exclusion>>);
+
+impl ExclusionList {
+    pub fn exclude(&mut self, sequencer_id: SequencerId, exclusion: Exclusion) {
+        self.0.entry(sequencer_id).or_default().push(exclusion);
+    }
+
+    /// The exclusions of the sequencer, including the expired ones.
+    pub fn history(&self, sequencer_id: &SequencerId) -> &[Exclusion] {
+        self.0
+            .get(sequencer_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The exclusion which keeps the sequencer out at `block_height` the longest.
+    pub fn active(
+        &self,
+        sequencer_id: &SequencerId,
+        block_height: &BlockHeight,
+    ) -> Option<&Exclusion> {
+        self.history(sequencer_id)
+            .iter()
+            .filter(|exclusion| exclusion.is_active(block_height))
+            .max_by_key(|exclusion| exclusion.until().map_or(usize::MAX, |until| until.value()))
+    }
+
+    pub fn is_excluded(&self, sequencer_id: &SequencerId, block_height: &BlockHeight) -> bool {
+        self.active(sequencer_id, block_height).is_some()
     }
 }
 
@@ -234,19 +1019,168 @@ impl From<&String> for RawTransaction {
     }
 }
 
+impl RawTransaction {
+    pub fn hash(&self) -> TransactionHash {
+        TransactionHash(Sha256::digest(self.as_ref()).into())
+    }
+}
+
+/// The SHA-256 hash of a [`RawTransaction`], used to recognize resubmitted transactions.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct TransactionHash([u8; 32]);
+
+impl AsRef<[u8]> for TransactionHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for TransactionHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// The position in a block which the leader promised to a transaction, signed with
+/// the wallet key of the leader over [`OrderCommitment::digest`].
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct OrderCommitment {
     block_height: BlockHeight,
     tx_order: TransactionOrder,
+    tx_hash: TransactionHash,
+    signature: Signature,
+    signer: Address,
 }
 
 impl OrderCommitment {
-    pub fn new(block_height: BlockHeight, tx_order: TransactionOrder) -> Self {
+    pub fn new(
+        block_height: BlockHeight,
+        tx_order: TransactionOrder,
+        tx_hash: TransactionHash,
+        signature: Signature,
+        signer: Address,
+    ) -> Self {
         Self {
             block_height,
             tx_order,
+            tx_hash,
+            signature,
+            signer,
         }
     }
+
+    /// Separates order commitments from the other messages signed with the same key.
+    pub const DOMAIN: &'static str = "OrderCommitment";
+
+    /// `keccak256(abi.encode(DOMAIN, rollup_id, block_height, tx_order, tx_hash))`, which
+    /// the leader signs as an EIP-191 personal message.
+    pub fn digest(
+        rollup_id: &RollupId,
+        block_height: &BlockHeight,
+        tx_order: &TransactionOrder,
+        tx_hash: &TransactionHash,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            Token::String(Self::DOMAIN.to_string()),
+            Token::String(rollup_id.to_string()),
+            Token::Uint(U256::from(block_height.value())),
+            Token::Uint(U256::from(tx_order.value())),
+            Token::FixedBytes(tx_hash.as_ref().to_vec()),
+        ]))
+    }
+
+    pub fn block_height(&self) -> &BlockHeight {
+        &self.block_height
+    }
+
+    pub fn tx_order(&self) -> &TransactionOrder {
+        &self.tx_order
+    }
+
+    pub fn tx_hash(&self) -> &TransactionHash {
+        &self.tx_hash
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The wallet address of the leader which issued the commitment.
+    pub fn signer(&self) -> &Address {
+        &self.signer
+    }
+
+    /// Check that the commitment is for `raw_tx` of `rollup_id` and was signed by
+    /// [`OrderCommitment::signer`]. Whether the signer was the leader of the block is
+    /// up to the caller.
+    pub fn verify(&self, rollup_id: &RollupId, raw_tx: &RawTransaction) -> Result<(), Error> {
+        if raw_tx.hash() != self.tx_hash {
+            return Err(Error::new(
+                ErrorCode::InvalidSignature,
+                format!(
+                    "The order commitment is for transaction {}, not {}",
+                    self.tx_hash,
+                    raw_tx.hash(),
+                ),
+            ));
+        }
+
+        let digest = Self::digest(rollup_id, &self.block_height, &self.tx_order, &self.tx_hash);
+        self.signature.verify(digest.to_vec(), self.signer).wrap_as(
+            ErrorCode::InvalidSignature,
+            format!("The order commitment was not signed by {:?}", self.signer),
+        )
+    }
+}
+
+/// The opening of a block commitment at the position of a transaction.
+///
+/// With the vector and KZG commitment methods, a block is committed to in chunks of a
+/// fixed number of transactions, and the block commitment is the hash of the chunk
+/// commitments. A Merkle tree commits to the whole block as a single chunk whose root is
+/// the block commitment. The proof carries every chunk commitment and the witness of the
+/// transaction within its chunk, all in compressed form.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InclusionProof {
+    block_height: BlockHeight,
+    tx_order: TransactionOrder,
+    chunk_commitments: Vec<Bytes>,
+    witness: Bytes,
+}
+
+impl InclusionProof {
+    pub fn new(
+        block_height: BlockHeight,
+        tx_order: TransactionOrder,
+        chunk_commitments: Vec<Bytes>,
+        witness: Bytes,
+    ) -> Self {
+        Self {
+            block_height,
+            tx_order,
+            chunk_commitments,
+            witness,
+        }
+    }
+
+    pub fn block_height(&self) -> &BlockHeight {
+        &self.block_height
+    }
+
+    pub fn tx_order(&self) -> &TransactionOrder {
+        &self.tx_order
+    }
+
+    pub fn chunk_commitments(&self) -> &[Bytes] {
+        &self.chunk_commitments
+    }
+
+    pub fn witness(&self) -> &Bytes {
+        &self.witness
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Deserialize, Serialize)]
@@ -325,6 +1259,13 @@ impl BlockMetadata {
         self.tx_order = TransactionOrder::default();
     }
 
+    /// Replace the leader for the rest of the block height after a failover.
+    /// The transaction order issued so far is kept.
+    pub fn replace_leader(&mut self, is_leader: bool, leader_id: SequencerId) {
+        self.is_leader = is_leader;
+        self.leader_id = leader_id;
+    }
+
     pub fn block_height(&self) -> BlockHeight {
         self.block_height.clone()
     }
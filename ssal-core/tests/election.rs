@@ -0,0 +1,81 @@
+use ethers_core::types::Address;
+use ssal_core::{election::*, types::*};
+
+fn candidates(stakes: &[(&str, u64)]) -> Vec<Candidate> {
+    stakes
+        .iter()
+        .map(|(sequencer_id, stake)| {
+            Candidate::new((*sequencer_id).into(), Address::zero(), (*stake).into())
+        })
+        .collect()
+}
+
+/// The leaders of `block_count` consecutive block heights, all drawn from the genesis
+/// seed so that the result only depends on the election method.
+fn elect(
+    election_method: ElectionMethod,
+    candidates: &[Candidate],
+    block_count: usize,
+) -> Vec<SequencerId> {
+    let rollup_id = RollupId::from("1");
+    let seed = ElectionSeed::genesis(&rollup_id);
+    (1..=block_count)
+        .map(|block_height| {
+            let context = ElectionContext {
+                rollup_id: &rollup_id,
+                block_height: &block_height.into(),
+                seed: &seed,
+            };
+            election_method
+                .election()
+                .elect(candidates, &context)
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn round_robin_rotates_over_the_sorted_ids() {
+    let registered = candidates(&[("c", 0), ("a", 0), ("b", 0)]);
+    let leaders = elect(ElectionMethod::RoundRobin, &registered, 6);
+    let expected: Vec<SequencerId> = ["b", "c", "a", "b", "c", "a"]
+        .into_iter()
+        .map(SequencerId::from)
+        .collect();
+    assert_eq!(leaders, expected);
+
+    // The registration order does not change the rotation.
+    let reordered = candidates(&[("a", 0), ("b", 0), ("c", 0)]);
+    assert_eq!(elect(ElectionMethod::RoundRobin, &reordered, 6), expected);
+}
+
+#[test]
+fn stake_weighted_elections_follow_the_stake() {
+    let registered = candidates(&[("a", 1), ("b", 3), ("c", 0), ("d", 4)]);
+    let block_count = 8000;
+    let leaders = elect(ElectionMethod::StakeWeighted, &registered, block_count);
+    let share = |sequencer_id: &str| {
+        let sequencer_id = SequencerId::from(sequencer_id);
+        leaders
+            .iter()
+            .filter(|leader| **leader == sequencer_id)
+            .count() as f64
+            / block_count as f64
+    };
+
+    assert_eq!(share("c"), 0.0);
+    for (sequencer_id, expected_share) in [("a", 0.125), ("b", 0.375), ("d", 0.5)] {
+        assert!(
+            (share(sequencer_id) - expected_share).abs() < 0.02,
+            "{} led {} of the blocks",
+            sequencer_id,
+            share(sequencer_id),
+        );
+    }
+
+    // The same inputs always elect the same leaders.
+    assert_eq!(
+        elect(ElectionMethod::StakeWeighted, &registered, block_count),
+        leaders
+    );
+}
@@ -2,7 +2,7 @@ use std::{any, fmt::Debug, ops, path::Path, sync::Arc};
 
 use ssal_core::{
     bincode,
-    error::{Error, WrapError},
+    error::{Error, ErrorCode, WrapError},
     rocksdb::{Options, Transaction, TransactionDB, TransactionDBOptions},
     serde::{de::DeserializeOwned, ser::Serialize},
 };
@@ -25,10 +25,10 @@ impl Database {
         db_options.create_if_missing(true);
         let tx_db_options = TransactionDBOptions::default();
 
-        let client = TransactionDB::open(&db_options, &tx_db_options, &path).wrap(format!(
-            "Failed to open the database at {:?}",
-            path.as_ref(),
-        ))?;
+        let client = TransactionDB::open(&db_options, &tx_db_options, &path).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to open the database at {:?}", path.as_ref()),
+        )?;
 
         Ok(Self {
             client: Arc::new(client),
@@ -40,19 +40,27 @@ impl Database {
         K: Debug + Serialize,
         V: Debug + DeserializeOwned,
     {
-        let key_vec =
-            bincode::serialize(key).wrap(format!("Failed to serialize the key: {:?}", key))?;
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
 
         let value_slice = self
             .client
             .get_pinned(key_vec)
-            .wrap(format!("Failed to get the key: {:?}", key))?
-            .wrap(format!("key: {:?}", key))?;
+            .wrap_as(
+                ErrorCode::StorageFailure,
+                format!("Failed to get the key: {:?}", key),
+            )?
+            .wrap_as(ErrorCode::NotFound, format!("key: {:?}", key))?;
 
-        let value: V = bincode::deserialize(value_slice.as_ref()).wrap(format!(
-            "Failed to deserialize the value into type: {:?}",
-            any::type_name::<V>(),
-        ))?;
+        let value: V = bincode::deserialize(value_slice.as_ref()).wrap_as(
+            ErrorCode::StorageFailure,
+            format!(
+                "Failed to deserialize the value into type: {:?}",
+                any::type_name::<V>(),
+            ),
+        )?;
 
         Ok(value)
     }
@@ -62,19 +70,27 @@ impl Database {
         K: Debug + Serialize,
         V: Debug + DeserializeOwned + Serialize,
     {
-        let key_vec =
-            bincode::serialize(key).wrap(format!("Failed to serialize the key: {:?}", key))?;
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
 
         let transaction = self.client.transaction();
         let value_slice = transaction
             .get_for_update(&key_vec, true)
-            .wrap(format!("Failed to get a lock for the key: {:?}", key))?
-            .wrap(format!("key: {:?}", key))?;
+            .wrap_as(
+                ErrorCode::StorageFailure,
+                format!("Failed to get a lock for the key: {:?}", key),
+            )?
+            .wrap_as(ErrorCode::NotFound, format!("key: {:?}", key))?;
 
-        let value: V = bincode::deserialize(value_slice.as_ref()).wrap(format!(
-            "Failed to deserialize the value into type: {:?}",
-            any::type_name::<V>(),
-        ))?;
+        let value: V = bincode::deserialize(value_slice.as_ref()).wrap_as(
+            ErrorCode::StorageFailure,
+            format!(
+                "Failed to deserialize the value into type: {:?}",
+                any::type_name::<V>(),
+            ),
+        )?;
         let locked_value = Lock::new(Some(transaction), key_vec, value);
         Ok(locked_value)
     }
@@ -84,21 +100,28 @@ impl Database {
         K: Debug + Serialize,
         V: Debug + Serialize,
     {
-        let key_vec =
-            bincode::serialize(key).wrap(format!("Failed to serialize the key: {:?}", key))?;
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
 
-        let value_vec =
-            bincode::serialize(value).wrap(format!("Failed to serialize the value: {:?}", key))?;
+        let value_vec = bincode::serialize(value).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the value: {:?}", key),
+        )?;
 
         let transaction = self.client.transaction();
-        transaction.put(key_vec, value_vec).wrap(format!(
-            "Failed to put the value: {:?} for the key: {:?}",
-            key, value,
-        ))?;
-        transaction.commit().wrap(format!(
-            "Failed to commit put transaction for the key: {:?}",
-            key,
-        ))?;
+        transaction.put(key_vec, value_vec).wrap_as(
+            ErrorCode::StorageFailure,
+            format!(
+                "Failed to put the value: {:?} for the key: {:?}",
+                key, value,
+            ),
+        )?;
+        transaction.commit().wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to commit put transaction for the key: {:?}", key,),
+        )?;
         Ok(())
     }
 
@@ -106,17 +129,20 @@ impl Database {
     where
         K: Debug + Serialize,
     {
-        let key_vec =
-            bincode::serialize(key).wrap(format!("Failed to serialize the key: {:?}", key))?;
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
 
         let transaction = self.client.transaction();
-        transaction
-            .delete(key_vec)
-            .wrap(format!("Failed to delete the key: {:?}", key))?;
-        transaction.commit().wrap(format!(
-            "Failed to commit delete transaction for the key: {:?}",
-            key,
-        ))?;
+        transaction.delete(key_vec).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to delete the key: {:?}", key),
+        )?;
+        transaction.commit().wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to commit delete transaction for the key: {:?}", key,),
+        )?;
         Ok(())
     }
 }
@@ -157,18 +183,103 @@ where
         }
     }
 
+    /// Get and lock another key within the transaction of the lock, so that writing it
+    /// back with [`Lock::put`] commits atomically with the locked value.
+    pub fn get_for_update<K, T>(&self, key: &K) -> Result<T, Error>
+    where
+        K: Debug + Serialize,
+        T: Debug + DeserializeOwned,
+    {
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
+
+        let value_slice = self
+            .transaction()?
+            .get_for_update(key_vec, true)
+            .wrap_as(
+                ErrorCode::StorageFailure,
+                format!("Failed to get a lock for the key: {:?}", key),
+            )?
+            .wrap_as(ErrorCode::NotFound, format!("key: {:?}", key))?;
+
+        let value: T = bincode::deserialize(value_slice.as_ref()).wrap_as(
+            ErrorCode::StorageFailure,
+            format!(
+                "Failed to deserialize the value into type: {:?}",
+                any::type_name::<T>(),
+            ),
+        )?;
+        Ok(value)
+    }
+
+    /// Put another key within the transaction of the lock. The value is written when
+    /// the lock is committed and dropped with it otherwise.
+    pub fn put<K, T>(&self, key: &K, value: &T) -> Result<(), Error>
+    where
+        K: Debug + Serialize,
+        T: Debug + Serialize,
+    {
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
+
+        let value_vec = bincode::serialize(value).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the value: {:?}", key),
+        )?;
+
+        self.transaction()?.put(key_vec, value_vec).wrap_as(
+            ErrorCode::StorageFailure,
+            format!(
+                "Failed to put the value: {:?} for the key: {:?}",
+                key, value,
+            ),
+        )
+    }
+
+    /// Delete another key within the transaction of the lock.
+    pub fn delete<K>(&self, key: &K) -> Result<(), Error>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = bincode::serialize(key).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to serialize the key: {:?}", key),
+        )?;
+
+        self.transaction()?.delete(key_vec).wrap_as(
+            ErrorCode::StorageFailure,
+            format!("Failed to delete the key: {:?}", key),
+        )
+    }
+
+    fn transaction(&self) -> Result<&Transaction<'db, TransactionDB>, Error> {
+        self.transaction
+            .as_ref()
+            .wrap_as(ErrorCode::StorageFailure, "The lock is already committed")
+    }
+
     pub fn commit(mut self) -> Result<(), Error> {
         if let Some(transaction) = self.transaction.take() {
-            let value = bincode::serialize(&self.value)
-                .wrap(format!("Failed to serialize the value: {:?}", &self.value))?;
-
-            transaction
-                .put(self.key_vec, value)
-                .wrap(format!("Failed to put the value: {:?}", &self.value))?;
-            transaction.commit().wrap(format!(
-                "Failed to commit the transaction for the value: {:?}",
-                &self.value,
-            ))?;
+            let value = bincode::serialize(&self.value).wrap_as(
+                ErrorCode::StorageFailure,
+                format!("Failed to serialize the value: {:?}", &self.value),
+            )?;
+
+            transaction.put(self.key_vec, value).wrap_as(
+                ErrorCode::StorageFailure,
+                format!("Failed to put the value: {:?}", &self.value),
+            )?;
+            transaction.commit().wrap_as(
+                ErrorCode::StorageFailure,
+                format!(
+                    "Failed to commit the transaction for the value: {:?}",
+                    &self.value,
+                ),
+            )?;
         }
         Ok(())
     }
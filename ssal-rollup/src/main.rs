@@ -1,8 +1,10 @@
-use std::{any, collections::HashMap, env};
+use std::env;
 
+use ssal_api::{HttpClient, SsalClient};
 use ssal_core::{
+    election::ElectionMethod,
     error::{Error, WrapError},
-    reqwest::{Client, Url},
+    reqwest::Url,
     tokio::{
         self,
         time::{sleep, Duration},
@@ -27,82 +29,44 @@ async fn main() -> Result<(), Error> {
         .as_str()
         .try_into()
         .wrap("Failed to parse SSAL environment variable String into URL")?;
+    let election_method: ElectionMethod = match env_variables.get(2) {
+        Some(election_method) => election_method.parse()?,
+        None => ElectionMethod::default(),
+    };
+    let commitment_method: CommitmentMethod = match env_variables.get(3) {
+        Some(commitment_method) => commitment_method.parse()?,
+        None => CommitmentMethod::default(),
+    };
+    let curve: Curve = match env_variables.get(4) {
+        Some(curve) => curve.parse()?,
+        None => Curve::default(),
+    };
+    let durability_mode: DurabilityMode = match env_variables.get(5) {
+        Some(durability_mode) => durability_mode.parse()?,
+        None => DurabilityMode::default(),
+    };
 
-    register(&ssal_url, &rollup_id).await?;
-    let mut block_height = 1;
-    loop {
-        sleep(Duration::from_secs(5)).await;
-        if let Some(leader_id) = close_block(&ssal_url, &rollup_id).await? {
-            tracing::info!("{:?}, {:?}, {:?}", rollup_id, block_height, leader_id);
-            block_height += 1;
-        }
-    }
-}
-
-async fn register(ssal_base_url: &Url, rollup_id: &RollupId) -> Result<(), Error> {
-    let url = ssal_base_url
-        .join("register-rollup")
-        .wrap("[RegisterRollup] Failed to parse into URL")?;
-
-    let mut payload: HashMap<&'static str, String> = HashMap::new();
-    payload.insert("rollup_id", rollup_id.to_string());
-
-    let response = Client::new()
-        .post(url)
-        .json(&payload)
-        .send()
+    let ssal_client = SsalClient::new(ssal_url, HttpClient::new(HttpClient::DEFAULT_TIMEOUT)?);
+    match ssal_client
+        .register_rollup(
+            &rollup_id,
+            &RollupConfig::new(election_method, commitment_method, curve, durability_mode),
+        )
         .await
-        .wrap("[RegisterRollup]: Failed to send a request")?;
-
-    match response.error_for_status_ref() {
-        Ok(_) => {
-            tracing::info!("[RegisterRollup]: Successfully registered {:?}", rollup_id);
-            Ok(())
-        }
-        Err(_) => {
-            let error = response
-                .text()
-                .await
-                .wrap("[RegisterRollup]: Failed to parse the response into String")?;
-            tracing::error!("[RegisterRollup]: {}", error);
-            Ok(())
-        }
+    {
+        Ok(()) => tracing::info!("[RegisterRollup]: Successfully registered {:?}", rollup_id),
+        Err(error) => tracing::error!("[RegisterRollup]: {}", error),
     }
-}
-
-async fn close_block(
-    ssal_base_url: &Url,
-    rollup_id: &RollupId,
-) -> Result<Option<SequencerId>, Error> {
-    let url = ssal_base_url
-        .join("close-block")
-        .wrap("[CloseBlock] Failed to parse into URL")?;
-
-    let mut payload = HashMap::<&'static str, String>::new();
-    payload.insert("rollup_id", rollup_id.to_string());
-
-    let response = Client::new()
-        .post(url)
-        .json(&payload)
-        .send()
-        .await
-        .wrap("[CloseBlock]: Failed to send a request")?;
 
-    match response.error_for_status_ref() {
-        Ok(_) => {
-            let leader_id = response.json::<SequencerId>().await.wrap(format!(
-                "[CloseBlock]: Failed to parse the response into type: {}",
-                any::type_name::<SequencerId>(),
-            ))?;
-            Ok(Some(leader_id))
-        }
-        Err(_) => {
-            let error = response
-                .text()
-                .await
-                .wrap("[CloseBlock]: Failed to parse the response into String")?;
-            tracing::error!("[CloseBlock]: {}", error);
-            Ok(None)
+    let mut block_height = 1;
+    loop {
+        sleep(Duration::from_secs(5)).await;
+        match ssal_client.close_block(&rollup_id).await {
+            Ok(leader_id) => {
+                tracing::info!("{:?}, {:?}, {:?}", rollup_id, block_height, leader_id);
+                block_height += 1;
+            }
+            Err(error) => tracing::error!("[CloseBlock]: {}", error),
         }
     }
 }
@@ -6,11 +6,19 @@ use ethers::{
     providers::{Http, Provider},
     signers::Wallet,
 };
+use ssal_api::{HttpClient, SsalClient};
+use ssal_commitment::srs::CommitmentParamSet;
+use ssal_core::types::SequencerId;
 use ssal_database::Database;
 
 pub struct AppState {
     client: Arc<SignerMiddleware<Provider<Http>, Wallet<SigningKey>>>,
     database: Database,
+    http_client: HttpClient,
+    ssal_client: SsalClient,
+    sequencer_id: Arc<SequencerId>,
+    dedup_window: usize,
+    commitment_params: Arc<CommitmentParamSet>,
 }
 
 impl Clone for AppState {
@@ -18,6 +26,11 @@ impl Clone for AppState {
         Self {
             client: self.client.clone(),
             database: self.database.clone(),
+            http_client: self.http_client.clone(),
+            ssal_client: self.ssal_client.clone(),
+            sequencer_id: self.sequencer_id.clone(),
+            dedup_window: self.dedup_window,
+            commitment_params: self.commitment_params.clone(),
         }
     }
 }
@@ -26,10 +39,20 @@ impl AppState {
     pub fn new(
         client: SignerMiddleware<Provider<Http>, Wallet<SigningKey>>,
         database: Database,
+        http_client: HttpClient,
+        ssal_client: SsalClient,
+        sequencer_id: SequencerId,
+        dedup_window: usize,
+        commitment_params: CommitmentParamSet,
     ) -> Self {
         Self {
             client: Arc::new(client),
             database,
+            http_client,
+            ssal_client,
+            sequencer_id: Arc::new(sequencer_id),
+            dedup_window,
+            commitment_params: Arc::new(commitment_params),
         }
     }
 
@@ -40,4 +63,26 @@ impl AppState {
     pub fn database(&self) -> &Database {
         &self.database
     }
+
+    /// The connection pool for requests to the other sequencers.
+    pub fn http_client(&self) -> &HttpClient {
+        &self.http_client
+    }
+
+    pub fn ssal_client(&self) -> &SsalClient {
+        &self.ssal_client
+    }
+
+    pub fn sequencer_id(&self) -> &SequencerId {
+        &self.sequencer_id
+    }
+
+    /// The number of blocks within which a resubmitted transaction is recognized.
+    pub fn dedup_window(&self) -> usize {
+        self.dedup_window
+    }
+
+    pub fn commitment_params(&self) -> &CommitmentParamSet {
+        &self.commitment_params
+    }
 }
@@ -0,0 +1,135 @@
+//! Per-rollup durable queue of the blocks to build. A block is enqueued in the same
+//! transaction that moves the block metadata to the next block height, and every
+//! [`BuildState`] it goes through is persisted, so that `block_builder` resumes
+//! unfinished blocks where they left off after a restart.
+use std::fmt::Debug;
+
+use ssal_core::{
+    error::Error,
+    serde::{Deserialize, Serialize},
+    types::*,
+};
+use ssal_database::{Database, Lock};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub enum BuildState {
+    /// The block height has ended and its transactions are yet to be collected.
+    Pending,
+    /// The block is stored.
+    Built,
+    /// The block commitment is stored.
+    Committed,
+    /// The block commitment was sent to the contract, or the sequencer was a follower
+    /// with nothing to send.
+    Submitted,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(crate = "ssal_core::serde")]
+pub struct BuildJob {
+    block_height: BlockHeight,
+    tx_count: TransactionOrder,
+    is_leader: bool,
+    state: BuildState,
+}
+
+impl BuildJob {
+    pub fn new(block_height: BlockHeight, tx_count: TransactionOrder, is_leader: bool) -> Self {
+        Self {
+            block_height,
+            tx_count,
+            is_leader,
+            state: BuildState::Pending,
+        }
+    }
+
+    pub fn block_height(&self) -> &BlockHeight {
+        &self.block_height
+    }
+
+    pub fn tx_count(&self) -> &TransactionOrder {
+        &self.tx_count
+    }
+
+    /// Whether the sequencer was the leader at the end of the block height, and so
+    /// submits the block commitment.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+
+    pub fn state(&self) -> BuildState {
+        self.state
+    }
+}
+
+/// Enqueue `build_job` within the transaction of `lock`. A block height which was
+/// enqueued before keeps its progress.
+pub fn push<V>(lock: &Lock<V>, rollup_id: &RollupId, build_job: &BuildJob) -> Result<(), Error>
+where
+    V: Debug + Serialize,
+{
+    let build_job_key = ("build_job", rollup_id, build_job.block_height());
+    match lock.get_for_update::<_, BuildJob>(&build_job_key) {
+        Ok(_) => return Ok(()),
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error),
+    }
+
+    let mut queue: Vec<BlockHeight> = match lock.get_for_update(&("build_queue", rollup_id)) {
+        Ok(queue) => queue,
+        Err(error) if error.is_none_type() => Vec::new(),
+        Err(error) => return Err(error),
+    };
+    queue.push(build_job.block_height().clone());
+    lock.put(&("build_queue", rollup_id), &queue)?;
+    lock.put(&build_job_key, build_job)
+}
+
+/// Returns the oldest unfinished block of the rollup.
+pub fn front(database: &Database, rollup_id: &RollupId) -> Result<Option<BuildJob>, Error> {
+    let queue: Vec<BlockHeight> = match database.get(&("build_queue", rollup_id)) {
+        Ok(queue) => queue,
+        Err(error) if error.is_none_type() => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    match queue.first() {
+        Some(block_height) => database
+            .get(&("build_job", rollup_id, block_height))
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Returns the unfinished blocks of the rollup, oldest first.
+pub fn unfinished(database: &Database, rollup_id: &RollupId) -> Result<Vec<BuildJob>, Error> {
+    let queue: Vec<BlockHeight> = match database.get(&("build_queue", rollup_id)) {
+        Ok(queue) => queue,
+        Err(error) if error.is_none_type() => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    queue
+        .iter()
+        .map(|block_height| database.get(&("build_job", rollup_id, block_height)))
+        .collect()
+}
+
+/// Persist that `build_job` reached `state`. A submitted block leaves the queue in the
+/// same transaction.
+pub fn advance(
+    database: &Database,
+    rollup_id: &RollupId,
+    build_job: &mut BuildJob,
+    state: BuildState,
+) -> Result<(), Error> {
+    build_job.state = state;
+    let build_job_key = ("build_job", rollup_id, build_job.block_height());
+    if state != BuildState::Submitted {
+        return database.put(&build_job_key, build_job);
+    }
+
+    let mut queue: Lock<Vec<BlockHeight>> = database.get_mut(&("build_queue", rollup_id))?;
+    queue.retain(|block_height| block_height != build_job.block_height());
+    queue.put(&build_job_key, build_job)?;
+    queue.commit()
+}
@@ -38,12 +38,12 @@ pub async fn send_block_commitment(
     client: Arc<SignerMiddleware<Provider<Http>, Wallet<SigningKey>>>,
     rollup_id: &RollupId,
     block_height: &BlockHeight,
-    block_commitment: &Vec<u8>,
+    block_commitment: &BlockCommitment,
 ) -> Result<(), Error> {
     let contract_address = H160::from_str("0x84eA74d481Ee0A5332c457a4d796187F6Ba67fEB")
         .wrap("Failed to create a contract address")?;
     let contract = IIncredibleSquaringTaskManager::new(contract_address, client.clone());
-    let block_commitment_bytes = Bytes::from_iter(block_commitment);
+    let block_commitment_bytes = Bytes::from(block_commitment.to_bytes());
     let rollup_id_u32 = <RollupId as AsRef<str>>::as_ref(rollup_id)
         .parse::<u32>()
         .wrap("Failed to parse RollupId to u32")?;
@@ -54,10 +54,43 @@ pub async fn send_block_commitment(
             block_height.value() as u32,
             rollup_id_u32,
             100,
-            Bytes::from((0 as i32).to_be_bytes()),
+            Bytes::from(0_i32.to_be_bytes()),
         )
         .send()
         .await
         .wrap("Failed to create a new task")?;
     Ok(())
 }
+
+/// Sign the position of `raw_tx` with the wallet key of the sequencer.
+pub async fn sign_order_commitment(
+    client: Arc<SignerMiddleware<Provider<Http>, Wallet<SigningKey>>>,
+    rollup_id: &RollupId,
+    block_height: BlockHeight,
+    tx_order: TransactionOrder,
+    raw_tx: &RawTransaction,
+) -> Result<OrderCommitment, Error> {
+    let tx_hash = raw_tx.hash();
+    let digest = OrderCommitment::digest(rollup_id, &block_height, &tx_order, &tx_hash);
+    let signature = sign_digest(client.clone(), digest).await?;
+    Ok(OrderCommitment::new(
+        block_height,
+        tx_order,
+        tx_hash,
+        signature,
+        client.address(),
+    ))
+}
+
+/// Sign `digest` as an EIP-191 personal message with the wallet key of the sequencer,
+/// which is how the SSAL server and the other sequencers authenticate it.
+pub async fn sign_digest(
+    client: Arc<SignerMiddleware<Provider<Http>, Wallet<SigningKey>>>,
+    digest: [u8; 32],
+) -> Result<Signature, Error> {
+    client
+        .signer()
+        .sign_message(digest)
+        .await
+        .wrap("Failed to sign the digest")
+}
@@ -1,21 +1,19 @@
 use super::prelude::*;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(crate = "ssal_core::serde")]
-pub struct GetBlock {
-    rollup_id: RollupId,
-    block_height: BlockHeight,
-}
-
-impl GetBlock {
-    pub async fn handler(
-        State(state): State<AppState>,
-        Query(parameter): Query<Self>,
-    ) -> Result<impl IntoResponse, Error> {
-        let block: Vec<RawTransaction> =
-            state
-                .database()
-                .get(&("block", &parameter.rollup_id, &parameter.block_height))?;
-        Ok((StatusCode::OK, Json(block)))
-    }
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetBlock>,
+) -> Result<impl IntoResponse, Error> {
+    // A block is served once it is committed to, so that a block recovered from the
+    // other sequencers is only served after it matched the block commitment.
+    let _block_commitment: BlockCommitment = state.database().get(&(
+        "block_commitment",
+        &parameter.rollup_id,
+        &parameter.block_height,
+    ))?;
+    let block: Vec<RawTransaction> =
+        state
+            .database()
+            .get(&("block", &parameter.rollup_id, &parameter.block_height))?;
+    Ok((StatusCode::OK, Json(block)))
 }
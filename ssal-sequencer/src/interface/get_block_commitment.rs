@@ -1,27 +1,24 @@
-use super::prelude::*;
+use ssal_core::ethers_core::types::Bytes;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(crate = "ssal_core::serde")]
-pub struct GetBlockCommitment {
-    rollup_id: RollupId,
-    block_height: BlockHeight,
-}
+use super::prelude::*;
 
-impl GetBlockCommitment {
-    pub async fn handler(
-        State(state): State<AppState>,
-        Query(parameter): Query<Self>,
-    ) -> Result<impl IntoResponse, Error> {
-        tracing::info!(
-            "[Follower]: Operator retrieved the block commitment for {:?}: {:?}",
-            &parameter.rollup_id,
-            &parameter.block_height,
-        );
-        let block_commitment: String = state.database().get(&(
-            "block_commitment",
-            &parameter.rollup_id,
-            &parameter.block_height,
-        ))?;
-        Ok((StatusCode::OK, block_commitment))
-    }
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetBlockCommitment>,
+) -> Result<impl IntoResponse, Error> {
+    tracing::info!(
+        "[Follower]: Operator retrieved the block commitment for {:?}: {:?}",
+        &parameter.rollup_id,
+        &parameter.block_height,
+    );
+    let block_commitment: BlockCommitment = state.database().get(&(
+        "block_commitment",
+        &parameter.rollup_id,
+        &parameter.block_height,
+    ))?;
+    // The same tagged bytes as posted on chain, hex-encoded.
+    Ok((
+        StatusCode::OK,
+        Bytes::from(block_commitment.to_bytes()).to_string(),
+    ))
 }
@@ -0,0 +1,36 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetBlocks>,
+) -> Result<impl IntoResponse, Error> {
+    let from = parameter.from_block_height.value();
+    let to = parameter
+        .to_block_height
+        .value()
+        .min(from + GetBlocks::MAX_BLOCKS);
+
+    // Blocks which are not committed to yet are left out.
+    let mut blocks = Vec::new();
+    for block_height in (from..to).map(BlockHeight::from) {
+        let block_commitment: BlockCommitment =
+            match state
+                .database()
+                .get(&("block_commitment", &parameter.rollup_id, &block_height))
+            {
+                Ok(block_commitment) => block_commitment,
+                Err(error) if error.is_none_type() => continue,
+                Err(error) => return Err(error),
+            };
+        let block: Vec<RawTransaction> =
+            state
+                .database()
+                .get(&("block", &parameter.rollup_id, &block_height))?;
+        blocks.push(CommittedBlock {
+            block_height,
+            block,
+            block_commitment,
+        });
+    }
+    Ok((StatusCode::OK, Json(blocks)))
+}
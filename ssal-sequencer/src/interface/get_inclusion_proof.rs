@@ -0,0 +1,23 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetInclusionProof>,
+) -> Result<impl IntoResponse, Error> {
+    let block: Vec<RawTransaction> =
+        state
+            .database()
+            .get(&("block", &parameter.rollup_id, &parameter.block_height))?;
+    let rollup_config: RollupConfig = state
+        .database()
+        .get(&("rollup_config", &parameter.rollup_id))?;
+    let inclusion_proof = ssal_commitment::get_inclusion_proof(
+        state.commitment_params(),
+        rollup_config.curve(),
+        rollup_config.commitment_method(),
+        &block,
+        parameter.block_height,
+        parameter.tx_order,
+    )?;
+    Ok((StatusCode::OK, Json(inclusion_proof)))
+}
@@ -0,0 +1,45 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetTransactions>,
+) -> Result<impl IntoResponse, Error> {
+    let mut transactions = Vec::new();
+    let mut tx_order = parameter.from_tx_order;
+    while transactions.len() < GetTransactions::MAX_TRANSACTIONS {
+        let raw_tx: RawTransaction = match state.database().get(&(
+            "raw_tx",
+            &parameter.rollup_id,
+            &parameter.block_height,
+            &tx_order,
+        )) {
+            Ok(raw_tx) => raw_tx,
+            Err(error) if error.is_none_type() => break,
+            Err(error) => return Err(error),
+        };
+
+        // Stop at a transaction whose order commitment was pruned or replaced by a later
+        // submission of the same transaction.
+        let order_commitment: OrderCommitment =
+            match state
+                .database()
+                .get(&("tx_index", &parameter.rollup_id, &raw_tx.hash()))
+            {
+                Ok(order_commitment) => order_commitment,
+                Err(error) if error.is_none_type() => break,
+                Err(error) => return Err(error),
+            };
+        if *order_commitment.block_height() != parameter.block_height
+            || *order_commitment.tx_order() != tx_order
+        {
+            break;
+        }
+
+        transactions.push(SequencedTransaction {
+            raw_tx,
+            order_commitment,
+        });
+        tx_order.increment();
+    }
+    Ok((StatusCode::OK, Json(transactions)))
+}
@@ -1,8 +1,14 @@
-mod get_block;
-mod get_block_commitment;
-mod send_transaction;
-mod sync_transaction;
+pub mod get_block;
+pub mod get_block_commitment;
+pub mod get_blocks;
+pub mod get_inclusion_proof;
+pub mod get_transactions;
+pub mod send_transaction;
+pub mod send_transactions;
+pub mod sync_transaction;
+pub mod sync_transactions;
 pub mod prelude {
+    pub use ssal_api::{sequencer::*, SequencerClient};
     pub use ssal_core::{
         axum::{
             extract::{Query, State},
@@ -10,7 +16,7 @@ pub mod prelude {
             response::IntoResponse,
             Json,
         },
-        error::{Error, WrapError},
+        error::{Error, ErrorCode, WrapError},
         rand::{self, seq::SliceRandom},
         serde::{Deserialize, Serialize},
         tracing,
@@ -18,6 +24,5 @@ pub mod prelude {
     };
     pub use ssal_database::{Database, Lock};
 
-    pub use crate::app_state::AppState;
+    pub use crate::{app_state::AppState, mempool};
 }
-pub use self::{get_block::*, get_block_commitment::*, send_transaction::*, sync_transaction::*};
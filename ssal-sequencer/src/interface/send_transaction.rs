@@ -1,55 +1,151 @@
+use ssal_api::ssal::ReportLeaderFailure;
+
 use super::prelude::*;
-use crate::request::{forward_transaction, sync_transaction};
+use crate::{
+    chain::{sign_digest, sign_order_commitment},
+    sync, task,
+};
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(crate = "ssal_core::serde")]
-pub struct SendTransaction {
-    rollup_id: RollupId,
-    raw_tx: RawTransaction,
-}
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<SendTransaction>,
+) -> Result<impl IntoResponse, Error> {
+    // A leader which has been replaced steps down before it signs another order.
+    let block_metadata: BlockMetadata = state
+        .database()
+        .get(&("block_metadata", &payload.rollup_id))?;
+    if block_metadata.is_leader() {
+        task::confirm_leader(&state, &payload.rollup_id, &block_metadata.block_height()).await?;
+    }
+
+    let mut block_metadata: Lock<BlockMetadata> = state
+        .database()
+        .get_mut(&("block_metadata", &payload.rollup_id))?;
 
-impl SendTransaction {
-    pub async fn handler(
-        State(state): State<AppState>,
-        Json(payload): Json<Self>,
-    ) -> Result<impl IntoResponse, Error> {
-        let mut block_metadata: Lock<BlockMetadata> = state
-            .database()
-            .get_mut(&("block_metadata", &payload.rollup_id))?;
-
-        if block_metadata.is_leader() {
-            let leader_id = block_metadata.leader_id();
-            let block_height = block_metadata.block_height();
-            let tx_order = block_metadata.issue_tx_order();
-
-            // Sync the transaction.
-            let sequencer_set: SequencerSet =
-                state
-                    .database()
-                    .get(&("sequencer_set", &payload.rollup_id, &block_height))?;
-            for follower_id in sequencer_set.iter() {
-                if *follower_id != leader_id {
-                    let _ =
-                        sync_transaction(follower_id, &payload.rollup_id, &payload.raw_tx).await;
-                }
-            }
-
-            state.database().put(
-                &("raw_tx", &payload.rollup_id, &block_height, &tx_order),
-                &payload.raw_tx,
-            )?;
-            block_metadata.commit()?;
-
-            // Return the order commitment.
-            let order_commitment = OrderCommitment::new(block_height, tx_order);
-            Ok((StatusCode::OK, Json(order_commitment)))
-        } else {
-            let leader_id = block_metadata.leader_id();
+    if block_metadata.is_leader() {
+        let block_height = block_metadata.block_height();
+
+        // Return the original order commitment of a resubmitted transaction, once it is
+        // synced as the first submission was.
+        if let Some(order_commitment) = mempool::get_order_commitment(
+            &block_metadata,
+            &payload.rollup_id,
+            &payload.raw_tx,
+            &block_height,
+            state.dedup_window(),
+        )? {
             drop(block_metadata);
+            sync::sync_sequenced(
+                &state,
+                &payload.rollup_id,
+                vec![SequencedTransaction {
+                    raw_tx: payload.raw_tx,
+                    order_commitment: order_commitment.clone(),
+                }],
+            )
+            .await?;
+            return Ok((StatusCode::OK, Json(order_commitment)));
+        }
+        let tx_order = block_metadata.issue_tx_order();
+        let order_commitment = sign_order_commitment(
+            state.client(),
+            &payload.rollup_id,
+            block_height.clone(),
+            tx_order.clone(),
+            &payload.raw_tx,
+        )
+        .await?;
+
+        block_metadata.put(
+            &("raw_tx", &payload.rollup_id, &block_height, &tx_order),
+            &payload.raw_tx,
+        )?;
+        mempool::put_order_commitment(
+            &block_metadata,
+            &payload.rollup_id,
+            &payload.raw_tx,
+            &order_commitment,
+        )?;
+        block_metadata.commit()?;
+
+        // Sync the transaction, waiting for the acknowledgements the rollup requires.
+        sync::sync_sequenced(
+            &state,
+            &payload.rollup_id,
+            vec![SequencedTransaction {
+                raw_tx: payload.raw_tx,
+                order_commitment: order_commitment.clone(),
+            }],
+        )
+        .await?;
+
+        // Return the order commitment.
+        Ok((StatusCode::OK, Json(order_commitment)))
+    } else if payload.forwarded {
+        // Never forward twice so that two sequencers disagreeing on the leader
+        // do not bounce the transaction between each other.
+        Err(Error::new(
+            ErrorCode::NotLeader,
+            format!(
+                "{:?} is not the leader for {:?}",
+                state.sequencer_id(),
+                block_metadata.block_height(),
+            ),
+        ))
+    } else {
+        let leader_id = block_metadata.leader_id();
+        let block_height = block_metadata.block_height();
+        drop(block_metadata);
+
+        let leader = SequencerClient::from_sequencer_id(&leader_id, state.http_client().clone())?;
+        let error = match leader
+            .forward_transaction(&payload.rollup_id, &payload.raw_tx)
+            .await
+        {
+            Ok(order_commitment) => return Ok((StatusCode::OK, Json(order_commitment))),
+            Err(error) => error,
+        };
+
+        report_unreachable_leader(
+            &state,
+            &payload.rollup_id,
+            &block_height,
+            &leader_id,
+            &error,
+        )
+        .await;
+        Err(error)
+    }
+}
 
-            let order_commitment =
-                forward_transaction(&leader_id, &payload.rollup_id, &payload.raw_tx).await?;
-            Ok((StatusCode::OK, Json(order_commitment)))
+/// Report the unreachable leader so that the SSAL can fail over once a quorum of the
+/// followers agrees. The replacement is picked up by `leader_watcher`.
+pub(super) async fn report_unreachable_leader(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    leader_id: &SequencerId,
+    error: &Error,
+) {
+    if error.code() == ErrorCode::UpstreamFailure {
+        let digest =
+            ReportLeaderFailure::digest(rollup_id, block_height, state.sequencer_id(), leader_id);
+        let result = match sign_digest(state.client(), digest).await {
+            Ok(signature) => state
+                .ssal_client()
+                .report_leader_failure(
+                    rollup_id,
+                    block_height,
+                    state.sequencer_id(),
+                    leader_id,
+                    &signature,
+                )
+                .await
+                .map(|_| ()),
+            Err(error) => Err(error),
+        };
+        if let Err(report_error) = result {
+            tracing::error!("{}", report_error);
         }
     }
 }
@@ -0,0 +1,124 @@
+use super::{prelude::*, send_transaction::report_unreachable_leader};
+use crate::{chain::sign_order_commitment, sync, task};
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<SendTransactions>,
+) -> Result<impl IntoResponse, Error> {
+    if payload.raw_txs.is_empty() || payload.raw_txs.len() > SendTransactions::MAX_TRANSACTIONS {
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "A batch holds 1 to {} transactions, got {}",
+                SendTransactions::MAX_TRANSACTIONS,
+                payload.raw_txs.len(),
+            ),
+        ));
+    }
+
+    // A leader which has been replaced steps down before it signs another order.
+    let block_metadata: BlockMetadata = state
+        .database()
+        .get(&("block_metadata", &payload.rollup_id))?;
+    if block_metadata.is_leader() {
+        task::confirm_leader(&state, &payload.rollup_id, &block_metadata.block_height()).await?;
+    }
+
+    let mut block_metadata: Lock<BlockMetadata> = state
+        .database()
+        .get_mut(&("block_metadata", &payload.rollup_id))?;
+
+    if block_metadata.is_leader() {
+        let block_height = block_metadata.block_height();
+
+        // Sequence the whole batch under the lock so that its transactions are
+        // contiguous.
+        let mut order_commitments = Vec::with_capacity(payload.raw_txs.len());
+        let mut transactions = Vec::new();
+        for raw_tx in payload.raw_txs {
+            // Return the original order commitment of a resubmitted transaction, once it
+            // is synced as the first submission was.
+            if let Some(order_commitment) = mempool::get_order_commitment(
+                &block_metadata,
+                &payload.rollup_id,
+                &raw_tx,
+                &block_height,
+                state.dedup_window(),
+            )? {
+                order_commitments.push(order_commitment.clone());
+                transactions.push(SequencedTransaction {
+                    raw_tx,
+                    order_commitment,
+                });
+                continue;
+            }
+            let tx_order = block_metadata.issue_tx_order();
+            let order_commitment = sign_order_commitment(
+                state.client(),
+                &payload.rollup_id,
+                block_height.clone(),
+                tx_order.clone(),
+                &raw_tx,
+            )
+            .await?;
+
+            block_metadata.put(
+                &("raw_tx", &payload.rollup_id, &block_height, &tx_order),
+                &raw_tx,
+            )?;
+            mempool::put_order_commitment(
+                &block_metadata,
+                &payload.rollup_id,
+                &raw_tx,
+                &order_commitment,
+            )?;
+            order_commitments.push(order_commitment.clone());
+            transactions.push(SequencedTransaction {
+                raw_tx,
+                order_commitment,
+            });
+        }
+        block_metadata.commit()?;
+
+        // Sync the batch in one request per follower and run of contiguous transaction
+        // orders, waiting for the acknowledgements the rollup requires.
+        sync::sync_sequenced(&state, &payload.rollup_id, transactions).await?;
+
+        // Return the order commitments in the order of the batch.
+        Ok((StatusCode::OK, Json(order_commitments)))
+    } else if payload.forwarded {
+        // Never forward twice so that two sequencers disagreeing on the leader
+        // do not bounce the batch between each other.
+        Err(Error::new(
+            ErrorCode::NotLeader,
+            format!(
+                "{:?} is not the leader for {:?}",
+                state.sequencer_id(),
+                block_metadata.block_height(),
+            ),
+        ))
+    } else {
+        let leader_id = block_metadata.leader_id();
+        let block_height = block_metadata.block_height();
+        drop(block_metadata);
+
+        let leader = SequencerClient::from_sequencer_id(&leader_id, state.http_client().clone())?;
+        let error = match leader
+            .forward_transactions(&payload.rollup_id, &payload.raw_txs)
+            .await
+        {
+            Ok(order_commitments) => return Ok((StatusCode::OK, Json(order_commitments))),
+            Err(error) => error,
+        };
+
+        report_unreachable_leader(
+            &state,
+            &payload.rollup_id,
+            &block_height,
+            &leader_id,
+            &error,
+        )
+        .await;
+        Err(error)
+    }
+}
@@ -0,0 +1,45 @@
+use super::prelude::*;
+use crate::sync;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<SyncTransactions>,
+) -> Result<impl IntoResponse, Error> {
+    let mut syncs = Vec::with_capacity(payload.transactions.len());
+    for transaction in payload.transactions {
+        let order_commitment = &transaction.order_commitment;
+        order_commitment.verify(&payload.rollup_id, &transaction.raw_tx)?;
+
+        // The transactions are contiguous so that none is buffered behind another one
+        // of the same batch.
+        let tx_order = order_commitment.tx_order().clone();
+        let is_contiguous = match syncs.last() {
+            Some(SyncTransaction {
+                tx_order: previous, ..
+            }) => tx_order.value() == previous.value() + 1,
+            None => true,
+        };
+        if *order_commitment.block_height() != payload.block_height || !is_contiguous {
+            return Err(Error::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "{:?}: {:?} is out of order in the batch of {:?}",
+                    order_commitment.block_height(),
+                    tx_order,
+                    payload.block_height,
+                ),
+            ));
+        }
+
+        syncs.push(SyncTransaction {
+            rollup_id: payload.rollup_id.clone(),
+            block_height: payload.block_height.clone(),
+            tx_order,
+            raw_tx: transaction.raw_tx,
+            order_commitment: transaction.order_commitment,
+        });
+    }
+
+    sync::receive_all(state.database(), &payload.rollup_id, &syncs)?;
+    Ok((StatusCode::OK, ()))
+}
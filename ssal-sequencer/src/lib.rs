@@ -1,5 +1,8 @@
 pub mod app_state;
+pub mod build_queue;
 pub mod chain;
 pub mod interface;
-pub mod request;
+pub mod mempool;
+pub mod resync;
+pub mod sync;
 pub mod task;
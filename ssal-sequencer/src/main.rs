@@ -1,5 +1,7 @@
 use std::env;
 
+use ssal_api::{sequencer::*, Endpoint, HttpClient, SsalClient};
+use ssal_commitment::srs::CommitmentParamSet;
 use ssal_core::{
     axum::{
         self,
@@ -14,7 +16,10 @@ use ssal_core::{
     types::*,
 };
 use ssal_database::Database;
-use ssal_sequencer::{app_state::AppState, chain::init_client, interface::*, task::registerer};
+use ssal_sequencer::{
+    app_state::AppState, chain::init_client, interface::*, mempool::DEFAULT_DEDUP_WINDOW,
+    task::registerer,
+};
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Error> {
@@ -54,23 +59,70 @@ async fn main() -> Result<(), Error> {
         .get(3)
         .wrap("Provide the private key for the wallet")?;
     let client = init_client(&chain_url, &wallet_private_key).await?;
+    let stake: Stake = match env_variables.get(4) {
+        Some(stake) => stake
+            .parse::<u64>()
+            .wrap("Failed to parse the stake into u64")?
+            .into(),
+        None => Stake::default(),
+    };
+    let dedup_window = match env_variables.get(5) {
+        Some(dedup_window) => dedup_window
+            .parse::<usize>()
+            .wrap("Failed to parse the dedup window into usize")?,
+        None => DEFAULT_DEDUP_WINDOW,
+    };
+
+    // The SRS of each curve is loaded once, when the rollup config asks for it.
+    let srs_dir = match env_variables.get(6) {
+        Some(srs_dir) => srs_dir.into(),
+        None => env::current_dir()
+            .wrap("Failed to get the current directory")?
+            .join("srs"),
+    };
+    // The rollup uses the SRS of a single curve, so one checksum pins the SRS of whichever
+    // curve its config asks for, and the SRS of any other curve fails the check.
+    let commitment_params = match env_variables.get(7) {
+        Some(srs_checksum) => Curve::ALL
+            .into_iter()
+            .fold(CommitmentParamSet::new(srs_dir), |params, curve| {
+                params.with_checksum(curve, srs_checksum.as_str())
+            }),
+        None => CommitmentParamSet::new(srs_dir),
+    };
 
-    let app_state = AppState::new(client, database);
+    // Every request to the SSAL and the other sequencers shares one connection pool.
+    let http_client = HttpClient::new(HttpClient::DEFAULT_TIMEOUT)?;
+    let ssal_client = SsalClient::new(ssal_url, http_client.clone());
+    let app_state = AppState::new(
+        client,
+        database,
+        http_client,
+        ssal_client,
+        sequencer_id.clone(),
+        dedup_window,
+        commitment_params,
+    );
 
     // Init registerer task.
     registerer(
         app_state.clone(),
-        ssal_url.clone(),
         rollup_id.clone(),
         sequencer_id.clone(),
+        stake,
     );
 
     // Set handlers
     let app = Router::new()
-        .route("/get-block-commitment", get(GetBlockCommitment::handler))
-        .route("/get-block", get(GetBlock::handler))
-        .route("/send-transaction", post(SendTransaction::handler))
-        .route("/sync-transaction", post(SyncTransaction::handler))
+        .route(GetBlockCommitment::PATH, get(get_block_commitment::handler))
+        .route(GetBlock::PATH, get(get_block::handler))
+        .route(GetBlocks::PATH, get(get_blocks::handler))
+        .route(GetInclusionProof::PATH, get(get_inclusion_proof::handler))
+        .route(GetTransactions::PATH, get(get_transactions::handler))
+        .route(SendTransaction::PATH, post(send_transaction::handler))
+        .route(SendTransactions::PATH, post(send_transactions::handler))
+        .route(SyncTransaction::PATH, post(sync_transaction::handler))
+        .route(SyncTransactions::PATH, post(sync_transactions::handler))
         .layer(CorsLayer::permissive())
         .with_state(app_state);
 
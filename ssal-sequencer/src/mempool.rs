@@ -0,0 +1,87 @@
+//! Per-rollup index from the hash of every sequenced [`RawTransaction`] to its
+//! [`OrderCommitment`], so that a resubmitted transaction gets its original order
+//! back instead of being sequenced twice. The index is read and written within the
+//! transaction of the `block_metadata` [`Lock`] so that it commits together with the
+//! transactions it points to.
+use std::fmt::Debug;
+
+use ssal_core::{error::Error, serde::Serialize, types::*};
+use ssal_database::{Database, Lock};
+
+/// The number of blocks a transaction is remembered for unless configured otherwise.
+pub const DEFAULT_DEDUP_WINDOW: usize = 100;
+
+/// Returns the order commitment of `raw_tx` if it was sequenced less than
+/// `dedup_window` blocks before `block_height`.
+pub fn get_order_commitment<V>(
+    block_metadata: &Lock<V>,
+    rollup_id: &RollupId,
+    raw_tx: &RawTransaction,
+    block_height: &BlockHeight,
+    dedup_window: usize,
+) -> Result<Option<OrderCommitment>, Error>
+where
+    V: Debug + Serialize,
+{
+    match block_metadata.get_for_update::<_, OrderCommitment>(&(
+        "tx_index",
+        rollup_id,
+        &raw_tx.hash(),
+    )) {
+        Ok(order_commitment)
+            if order_commitment.block_height().value() + dedup_window > block_height.value() =>
+        {
+            Ok(Some(order_commitment))
+        }
+        Ok(_) => Ok(None),
+        Err(error) if error.is_none_type() => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn put_order_commitment<V>(
+    block_metadata: &Lock<V>,
+    rollup_id: &RollupId,
+    raw_tx: &RawTransaction,
+    order_commitment: &OrderCommitment,
+) -> Result<(), Error>
+where
+    V: Debug + Serialize,
+{
+    block_metadata.put(&("tx_index", rollup_id, &raw_tx.hash()), order_commitment)
+}
+
+/// Forget the transactions of the block which left the dedup window when
+/// `block_height` was built.
+pub fn prune(
+    database: &Database,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    dedup_window: usize,
+) -> Result<(), Error> {
+    if block_height.value() < dedup_window {
+        return Ok(());
+    }
+
+    let expired_block_height = block_height.clone() - dedup_window;
+    let block: Vec<RawTransaction> =
+        match database.get(&("block", rollup_id, &expired_block_height)) {
+            Ok(block) => block,
+            Err(error) if error.is_none_type() => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+    for raw_tx in block.iter() {
+        let tx_index_key = ("tx_index", rollup_id, &raw_tx.hash());
+        // Keep the entry if the transaction was sequenced again after it expired.
+        match database.get::<_, OrderCommitment>(&tx_index_key) {
+            Ok(order_commitment) if *order_commitment.block_height() == expired_block_height => {
+                database.delete(&tx_index_key)?
+            }
+            Ok(_) => {}
+            Err(error) if error.is_none_type() => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
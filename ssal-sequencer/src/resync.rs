@@ -0,0 +1,363 @@
+//! Recovery of the transactions and blocks which a sequencer missed while it was down
+//! or before it joined, from the other members of the [`SequencerSet`] of each block
+//! height, the leader first.
+use ssal_api::{sequencer::*, ssal::SubmitBlockCommitment, SequencerClient};
+use ssal_commitment::srs::CommitmentParamSet;
+use ssal_core::{
+    error::{Error, ErrorCode, WrapError},
+    tracing,
+    types::*,
+};
+use ssal_database::Lock;
+
+use crate::{
+    app_state::AppState,
+    build_queue::{self, BuildJob, BuildState},
+    mempool, sync,
+};
+
+/// Catch up with the block height the sequencer stopped at, and recover the blocks it
+/// followed but did not build yet from the other sequencers. Runs on start, before
+/// `block_builder` builds the blocks left in the build queue.
+pub async fn resync(state: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    let block_metadata_key = ("block_metadata", rollup_id);
+    let block_metadata: BlockMetadata = match state.database().get(&block_metadata_key) {
+        Ok(block_metadata) => block_metadata,
+        Err(error) if error.is_none_type() => return Ok(()),
+        Err(error) => return Err(error),
+    };
+
+    // The block height has ended if the next one is closed.
+    let block_height = block_metadata.block_height();
+    match state
+        .ssal_client()
+        .get_closed_sequencer_set(rollup_id, &(block_height.clone() + 1))
+        .await
+    {
+        Ok(_) => {
+            // Enqueue the block as `leader_poller` would on the next block height, so
+            // that it is recovered below instead of being built from the transactions
+            // received before the restart.
+            let block_metadata: Lock<BlockMetadata> =
+                state.database().get_mut(&block_metadata_key)?;
+            let build_job = BuildJob::new(
+                block_metadata.block_height(),
+                block_metadata.tx_count(),
+                block_metadata.is_leader(),
+            );
+            build_queue::push(&block_metadata, rollup_id, &build_job)?;
+            block_metadata.commit()?;
+        }
+        Err(error) if error.code() == ErrorCode::NotFound => {
+            catch_up(state, rollup_id, &block_height).await?
+        }
+        Err(error) => return Err(error),
+    }
+
+    // The leader has every transaction of its block.
+    for mut build_job in build_queue::unfinished(state.database(), rollup_id)? {
+        if build_job.is_leader() || build_job.state() != BuildState::Pending {
+            continue;
+        }
+
+        match recover_block(state, rollup_id, build_job.block_height()).await {
+            Ok(()) => {
+                build_queue::advance(
+                    state.database(),
+                    rollup_id,
+                    &mut build_job,
+                    BuildState::Committed,
+                )?;
+                tracing::info!(
+                    "[Resync]: Recovered the block for {:?}: {:?}",
+                    rollup_id,
+                    build_job.block_height(),
+                );
+            }
+            Err(error) => tracing::error!(
+                "[Resync]: Failed to recover the block for {:?}: {:?}, building it from the stored transactions: {}",
+                rollup_id,
+                build_job.block_height(),
+                error,
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Fetch the transactions of `block_height` that the sequencer has not received, as
+/// long as it is a follower at `block_height`.
+pub async fn catch_up(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+) -> Result<(), Error> {
+    let block_metadata: BlockMetadata = match state.database().get(&("block_metadata", rollup_id)) {
+        Ok(block_metadata) => block_metadata,
+        Err(error) if error.is_none_type() => return Ok(()),
+        Err(error) => return Err(error),
+    };
+    if block_metadata.block_height() != block_height || block_metadata.is_leader() {
+        return Ok(());
+    }
+    let sequencer_set: SequencerSet =
+        state
+            .database()
+            .get(&("sequencer_set", rollup_id, block_height))?;
+
+    let mut result = Err(no_peer_error(block_height));
+    for peer_id in peers(&sequencer_set, state.sequencer_id()) {
+        result = catch_up_from(state, rollup_id, block_height, &peer_id).await;
+        match &result {
+            Ok(()) => break,
+            Err(error) => tracing::warn!(
+                "[Resync]: Failed to catch up with {:?} for {:?}: {:?}: {}",
+                peer_id,
+                rollup_id,
+                block_height,
+                error,
+            ),
+        }
+    }
+    result
+}
+
+async fn catch_up_from(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    peer_id: &SequencerId,
+) -> Result<(), Error> {
+    let peer = SequencerClient::from_sequencer_id(peer_id, state.http_client().clone())?;
+    let block_metadata_key = ("block_metadata", rollup_id);
+    loop {
+        let block_metadata: BlockMetadata = state.database().get(&block_metadata_key)?;
+        if block_metadata.block_height() != block_height {
+            return Ok(());
+        }
+        let transactions = peer
+            .get_transactions(rollup_id, block_height, &block_metadata.tx_count())
+            .await?;
+        let is_last_page = transactions.len() < GetTransactions::MAX_TRANSACTIONS;
+        for transaction in transactions.iter() {
+            let order_commitment = &transaction.order_commitment;
+            order_commitment.verify(rollup_id, &transaction.raw_tx)?;
+            if order_commitment.block_height() != block_height {
+                return Err(Error::new(
+                    ErrorCode::UpstreamFailure,
+                    format!(
+                        "{:?} returned a transaction of {:?} for {:?}",
+                        peer_id,
+                        order_commitment.block_height(),
+                        block_height,
+                    ),
+                ));
+            }
+        }
+
+        let mut block_metadata: Lock<BlockMetadata> =
+            state.database().get_mut(&block_metadata_key)?;
+        if block_metadata.block_height() != block_height {
+            return Ok(());
+        }
+        let mut result = Ok(());
+        for transaction in transactions.iter() {
+            // Transactions synced since the request are stored already.
+            let tx_count = block_metadata.tx_count();
+            let order_commitment = &transaction.order_commitment;
+            if order_commitment.tx_order().value() < tx_count.value() {
+                continue;
+            }
+            if *order_commitment.tx_order() != tx_count {
+                result = Err(Error::new(
+                    ErrorCode::UpstreamFailure,
+                    format!(
+                        "{:?} skipped from {:?} to {:?}",
+                        peer_id,
+                        tx_count,
+                        order_commitment.tx_order(),
+                    ),
+                ));
+                break;
+            }
+            if let Err(error) = sync::check_leader(&block_metadata, rollup_id, order_commitment) {
+                result = Err(error);
+                break;
+            }
+
+            let tx_order = block_metadata.issue_tx_order();
+            block_metadata.put(
+                &("raw_tx", rollup_id, block_height, &tx_order),
+                &transaction.raw_tx,
+            )?;
+            mempool::put_order_commitment(
+                &block_metadata,
+                rollup_id,
+                &transaction.raw_tx,
+                order_commitment,
+            )?;
+        }
+        sync::drain(&mut block_metadata, rollup_id)?;
+        block_metadata.commit()?;
+
+        if is_last_page || result.is_err() {
+            return result;
+        }
+    }
+}
+
+/// Fetch the block of `block_height` from the other sequencers and store it once it
+/// matches the final block commitment, which is the one sent to the contract. The SSAL
+/// serves the commitment with the signatures which made it final, and these are checked
+/// against the closed sequencer set, which also records the failovers the sequencer
+/// missed, so neither a peer nor the leader can pass off a block of its own.
+async fn recover_block(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+) -> Result<(), Error> {
+    let sequencer_set = state
+        .ssal_client()
+        .get_closed_sequencer_set(rollup_id, block_height)
+        .await?;
+    let endorsed_block_commitment = state
+        .ssal_client()
+        .get_endorsed_block_commitment(rollup_id, block_height)
+        .await?;
+    let block_commitment = check_endorsements(
+        rollup_id,
+        block_height,
+        &sequencer_set,
+        &endorsed_block_commitment,
+    )?;
+    let rollup_config: RollupConfig = state.database().get(&("rollup_config", rollup_id))?;
+
+    let mut result = Err(no_peer_error(block_height));
+    for peer_id in peers(&sequencer_set, state.sequencer_id()) {
+        result = fetch_block(state, rollup_id, block_height, &peer_id)
+            .await
+            .and_then(|committed_block| {
+                check_block(
+                    state.commitment_params(),
+                    &rollup_config,
+                    &committed_block.block,
+                    block_commitment,
+                )
+                .map(|()| committed_block.block)
+            });
+        match &result {
+            Ok(_) => break,
+            Err(error) => tracing::warn!(
+                "[Resync]: Failed to recover the block from {:?} for {:?}: {:?}: {}",
+                peer_id,
+                rollup_id,
+                block_height,
+                error,
+            ),
+        }
+    }
+    let block = result?;
+
+    state
+        .database()
+        .put(&("block", rollup_id, block_height), &block)?;
+    state.database().put(
+        &("block_commitment", rollup_id, block_height),
+        block_commitment,
+    )?;
+
+    // Transactions leaving the dedup window may be sequenced again.
+    mempool::prune(
+        state.database(),
+        rollup_id,
+        block_height,
+        state.dedup_window(),
+    )
+}
+
+/// The block commitment of `endorsed_block_commitment` if the leader of `sequencer_set`,
+/// or a failover quorum of its followers, signed it for `block_height`.
+pub fn check_endorsements<'a>(
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    sequencer_set: &SequencerSet,
+    endorsed_block_commitment: &'a EndorsedBlockCommitment,
+) -> Result<&'a BlockCommitment, Error> {
+    let block_commitment = endorsed_block_commitment.block_commitment();
+    let digest = SubmitBlockCommitment::digest(rollup_id, block_height, block_commitment);
+    match endorsed_block_commitment.is_final(sequencer_set, digest) {
+        true => Ok(block_commitment),
+        false => Err(Error::new(
+            ErrorCode::InvalidSignature,
+            format!(
+                "The block commitment for {:?} is not signed by the leader or a quorum of the followers",
+                block_height,
+            ),
+        )),
+    }
+}
+
+/// Check that `block` has the commitment `block_commitment` under the commitment method
+/// of the rollup.
+pub fn check_block(
+    commitment_params: &CommitmentParamSet,
+    rollup_config: &RollupConfig,
+    block: &[RawTransaction],
+    block_commitment: &BlockCommitment,
+) -> Result<(), Error> {
+    let recomputed = ssal_commitment::get_block_commitment(
+        commitment_params,
+        rollup_config.curve(),
+        rollup_config.commitment_method(),
+        block,
+    )?;
+    match recomputed == *block_commitment {
+        true => Ok(()),
+        false => Err(Error::new(
+            ErrorCode::UpstreamFailure,
+            "The block does not match the final block commitment",
+        )),
+    }
+}
+
+async fn fetch_block(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    peer_id: &SequencerId,
+) -> Result<CommittedBlock, Error> {
+    SequencerClient::from_sequencer_id(peer_id, state.http_client().clone())?
+        .get_blocks(rollup_id, block_height, &(block_height.clone() + 1))
+        .await?
+        .into_iter()
+        .find(|committed_block| committed_block.block_height == block_height)
+        .wrap_as(
+            ErrorCode::NotFound,
+            format!("{:?} has not committed to {:?}", peer_id, block_height),
+        )
+}
+
+/// The members of `sequencer_set` other than `sequencer_id`, the leader first.
+fn peers(sequencer_set: &SequencerSet, sequencer_id: &SequencerId) -> Vec<SequencerId> {
+    let leader_id = sequencer_set.leader();
+    leader_id
+        .iter()
+        .chain(
+            sequencer_set
+                .iter()
+                .filter(|member_id| Some(*member_id) != leader_id.as_ref()),
+        )
+        .filter(|member_id| *member_id != sequencer_id)
+        .cloned()
+        .collect()
+}
+
+fn no_peer_error(block_height: &BlockHeight) -> Error {
+    Error::new(
+        ErrorCode::NotFound,
+        format!(
+            "No other sequencer was in the sequencer set of {:?}",
+            block_height
+        ),
+    )
+}
@@ -0,0 +1,397 @@
+//! Replication of the transactions of the leader to its followers. The leader syncs
+//! every batch of transactions to each follower concurrently and retries until it is
+//! acknowledged. A follower stores a transaction only at the next transaction order of
+//! its block, and buffers the ones arriving ahead of it until the gap is filled.
+//! Depending on the [`DurabilityMode`] of the rollup, the leader answers only once
+//! enough followers acknowledged the transaction.
+use ssal_api::{
+    sequencer::{SequencedTransaction, SyncTransaction, SyncTransactions},
+    SequencerClient,
+};
+use ssal_core::{
+    error::{Error, ErrorCode},
+    tokio::{
+        self,
+        sync::mpsc,
+        time::{sleep, timeout, Duration},
+    },
+    tracing,
+    types::*,
+};
+use ssal_database::{Database, Lock};
+
+use crate::{app_state::AppState, mempool};
+
+/// How far ahead of its next transaction order a follower buffers synced transactions.
+/// Transactions of the next block height are buffered from its first transaction order.
+pub const MAX_BUFFERED_SYNCS: usize = 1024;
+
+/// How long the leader waits for the acknowledgements required by the durability mode
+/// of the rollup before answering.
+pub const SYNC_TIMEOUT: Duration = Duration::from_secs(2);
+
+const MAX_SYNC_ATTEMPTS: usize = 8;
+const MIN_SYNC_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Sync `transactions` of the leader to the followers of their block heights with
+/// [`sync_to_followers`], in one request per batch of [`batches`], and wait for the
+/// acknowledgements the rollup requires. Followers acknowledge the transactions they
+/// stored already again, so that a deduplicated resubmission is answered only once it
+/// is as durable as the first submission.
+pub async fn sync_sequenced(
+    state: &AppState,
+    rollup_id: &RollupId,
+    transactions: Vec<SequencedTransaction>,
+) -> Result<(), Error> {
+    let rollup_config: RollupConfig = state.database().get(&("rollup_config", rollup_id))?;
+    for batch in batches(rollup_id, transactions) {
+        let sequencer_set: SequencerSet =
+            state
+                .database()
+                .get(&("sequencer_set", rollup_id, &batch.block_height))?;
+        sync_to_followers(
+            state,
+            &sequencer_set,
+            rollup_config.durability_mode(),
+            batch,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Sort `transactions` by their order commitments, without duplicates, and split them
+/// into runs of contiguous transaction orders of one block height, which is what a
+/// follower accepts in one request.
+pub fn batches(
+    rollup_id: &RollupId,
+    mut transactions: Vec<SequencedTransaction>,
+) -> Vec<SyncTransactions> {
+    let position = |transaction: &SequencedTransaction| {
+        (
+            transaction.order_commitment.block_height().value(),
+            transaction.order_commitment.tx_order().value(),
+        )
+    };
+    transactions.sort_by_key(position);
+    transactions.dedup_by_key(|transaction| position(transaction));
+
+    let mut batches: Vec<SyncTransactions> = Vec::new();
+    for transaction in transactions {
+        let (block_height, tx_order) = position(&transaction);
+        match batches.last_mut() {
+            Some(batch)
+                if batch.block_height == block_height
+                    && batch
+                        .transactions
+                        .last()
+                        .is_some_and(|last| position(last).1 + 1 == tx_order) =>
+            {
+                batch.transactions.push(transaction)
+            }
+            _others => batches.push(SyncTransactions {
+                rollup_id: rollup_id.clone(),
+                block_height: block_height.into(),
+                transactions: vec![transaction],
+            }),
+        }
+    }
+    batches
+}
+
+/// Sync `sync` to every member of `sequencer_set` but the leader, concurrently, and
+/// wait up to [`SYNC_TIMEOUT`] for as many acknowledgements as `durability_mode`
+/// requires. The followers which have not acknowledged by then keep being retried.
+pub async fn sync_to_followers(
+    state: &AppState,
+    sequencer_set: &SequencerSet,
+    durability_mode: DurabilityMode,
+    sync: SyncTransactions,
+) -> Result<(), Error> {
+    let required_acks = durability_mode.required_acks(sequencer_set.candidates().len());
+    let (ack_sender, mut ack_receiver) = mpsc::unbounded_channel();
+    for follower_id in sequencer_set.iter() {
+        if follower_id != state.sequencer_id() {
+            let state = state.clone();
+            let follower_id = follower_id.clone();
+            let sync = sync.clone();
+            let ack_sender = ack_sender.clone();
+            tokio::spawn(async move {
+                match sync_to_follower(&state, &follower_id, &sync).await {
+                    // The receiver is gone once the quorum is reached or timed out.
+                    Ok(()) => {
+                        let _ = ack_sender.send(());
+                    }
+                    Err(error) => tracing::error!(
+                        "[Sync]: {:?} did not acknowledge {} transactions of {:?}: {}",
+                        follower_id,
+                        sync.transactions.len(),
+                        sync.block_height,
+                        error,
+                    ),
+                }
+            });
+        }
+    }
+    drop(ack_sender);
+
+    let mut acks = 0;
+    let _ = timeout(SYNC_TIMEOUT, async {
+        while acks < required_acks && ack_receiver.recv().await.is_some() {
+            acks += 1;
+        }
+    })
+    .await;
+    match acks >= required_acks {
+        true => Ok(()),
+        false => Err(Error::new(
+            ErrorCode::QuorumNotReached,
+            format!(
+                "{} of {} followers acknowledged the transactions of {:?}, the {} durability mode requires {}",
+                acks,
+                sequencer_set.candidates().len().saturating_sub(1),
+                sync.block_height,
+                durability_mode,
+                required_acks,
+            ),
+        )),
+    }
+}
+
+/// Retry with exponential backoff while the follower is unreachable or busy. A
+/// follower rejecting the transaction is not retried.
+async fn sync_to_follower(
+    state: &AppState,
+    follower_id: &SequencerId,
+    sync: &SyncTransactions,
+) -> Result<(), Error> {
+    let follower = SequencerClient::from_sequencer_id(follower_id, state.http_client().clone())?;
+    let mut backoff = MIN_SYNC_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match follower.request(sync).await {
+            Ok(()) => return Ok(()),
+            Err(error)
+                if attempt < MAX_SYNC_ATTEMPTS
+                    && matches!(
+                        error.code(),
+                        ErrorCode::UpstreamFailure | ErrorCode::StorageFailure
+                    ) =>
+            {
+                sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Store `sync` at the position the leader assigned, never at the next free one, so
+/// that the followers keep the order of the leader. The signature of the order
+/// commitment is checked by the caller, and whether its signer led the block height by
+/// [`check_leader`].
+pub fn receive(database: &Database, sync: &SyncTransaction) -> Result<(), Error> {
+    receive_all(database, &sync.rollup_id, std::slice::from_ref(sync))
+}
+
+/// [`receive`] each of `syncs` in order within one transaction. The transactions
+/// accepted before a rejected one are kept.
+pub fn receive_all(
+    database: &Database,
+    rollup_id: &RollupId,
+    syncs: &[SyncTransaction],
+) -> Result<(), Error> {
+    let mut block_metadata: Lock<BlockMetadata> =
+        database.get_mut(&("block_metadata", rollup_id))?;
+
+    for sync in syncs {
+        if sync.block_height == block_metadata.block_height()
+            && sync.tx_order == block_metadata.tx_count()
+        {
+            if let Err(error) = check_leader(&block_metadata, rollup_id, &sync.order_commitment) {
+                block_metadata.commit()?;
+                return Err(error);
+            }
+            store(&mut block_metadata, sync)?;
+            drain(&mut block_metadata, rollup_id)?;
+        } else if is_ahead(&block_metadata, sync) {
+            // The sequencer set of the next block height is not known yet, so those
+            // transactions are checked when they are drained.
+            if sync.block_height == block_metadata.block_height() {
+                if let Err(error) = check_leader(&block_metadata, rollup_id, &sync.order_commitment)
+                {
+                    block_metadata.commit()?;
+                    return Err(error);
+                }
+            }
+            buffer(&block_metadata, sync)?;
+        } else if let Err(error) = check_stored(database, sync) {
+            block_metadata.commit()?;
+            return Err(error);
+        }
+    }
+    block_metadata.commit()
+}
+
+/// Store `sync` at the next transaction order of `block_metadata`, within its
+/// transaction.
+fn store(block_metadata: &mut Lock<BlockMetadata>, sync: &SyncTransaction) -> Result<(), Error> {
+    let tx_order = block_metadata.issue_tx_order();
+    block_metadata.put(
+        &("raw_tx", &sync.rollup_id, &sync.block_height, &tx_order),
+        &sync.raw_tx,
+    )?;
+
+    // Keep the index in sync so that a new leader returns the commitment signed by the
+    // previous one for resubmitted transactions.
+    mempool::put_order_commitment(
+        block_metadata,
+        &sync.rollup_id,
+        &sync.raw_tx,
+        &sync.order_commitment,
+    )
+}
+
+/// Whether `sync` arrived ahead of the next transaction order of `block_metadata`
+/// within [`MAX_BUFFERED_SYNCS`].
+fn is_ahead(block_metadata: &BlockMetadata, sync: &SyncTransaction) -> bool {
+    let block_height = block_metadata.block_height();
+    let tx_count = block_metadata.tx_count().value();
+    let tx_order = sync.tx_order.value();
+    if sync.block_height == block_height {
+        tx_order > tx_count && tx_order - tx_count <= MAX_BUFFERED_SYNCS
+    } else {
+        sync.block_height == block_height + 1 && tx_order < MAX_BUFFERED_SYNCS
+    }
+}
+
+/// Buffer `sync` within the transaction of `block_metadata` until the transactions
+/// before it arrive.
+fn buffer(block_metadata: &Lock<BlockMetadata>, sync: &SyncTransaction) -> Result<(), Error> {
+    let position = (sync.block_height.clone(), sync.tx_order.clone());
+    let mut positions = buffered(block_metadata, &sync.rollup_id)?;
+    if !positions.contains(&position) {
+        positions.push(position);
+        block_metadata.put(&("sync_buffer_index", &sync.rollup_id), &positions)?;
+    }
+    block_metadata.put(
+        &(
+            "sync_buffer",
+            &sync.rollup_id,
+            &sync.block_height,
+            &sync.tx_order,
+        ),
+        sync,
+    )
+}
+
+/// The positions of the buffered transactions of the rollup, so that the ones left
+/// behind by an ended block height can be deleted.
+fn buffered(
+    block_metadata: &Lock<BlockMetadata>,
+    rollup_id: &RollupId,
+) -> Result<Vec<(BlockHeight, TransactionOrder)>, Error> {
+    match block_metadata.get_for_update(&("sync_buffer_index", rollup_id)) {
+        Ok(positions) => Ok(positions),
+        Err(error) if error.is_none_type() => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Store the buffered transactions which follow the transaction order of
+/// `block_metadata`, within its transaction, and delete the ones left behind by the
+/// block heights before it.
+pub fn drain(block_metadata: &mut Lock<BlockMetadata>, rollup_id: &RollupId) -> Result<(), Error> {
+    let mut positions = buffered(block_metadata, rollup_id)?;
+    if positions.is_empty() {
+        return Ok(());
+    }
+
+    loop {
+        let block_height = block_metadata.block_height();
+        let tx_count = block_metadata.tx_count();
+        let sync_buffer_key = ("sync_buffer", rollup_id, &block_height, &tx_count);
+        let sync: SyncTransaction = match block_metadata.get_for_update(&sync_buffer_key) {
+            Ok(sync) => sync,
+            Err(error) if error.is_none_type() => break,
+            Err(error) => return Err(error),
+        };
+        block_metadata.delete(&sync_buffer_key)?;
+        positions.retain(|position| *position != (block_height.clone(), tx_count.clone()));
+
+        // Drop a transaction the leader did not sign. The gap is filled by `catch_up`.
+        if let Err(error) = check_leader(block_metadata, rollup_id, &sync.order_commitment) {
+            tracing::warn!("[Sync]: Dropped a buffered transaction: {}", error);
+            break;
+        }
+        store(block_metadata, &sync)?;
+    }
+
+    let block_height = block_metadata.block_height();
+    for (buffered_height, tx_order) in positions.iter() {
+        if buffered_height.value() < block_height.value() {
+            block_metadata.delete(&("sync_buffer", rollup_id, buffered_height, tx_order))?;
+        }
+    }
+    positions.retain(|(buffered_height, _)| buffered_height.value() >= block_height.value());
+    block_metadata.put(&("sync_buffer_index", rollup_id), &positions)
+}
+
+/// Check that `order_commitment` was signed by a leader of its block height, as
+/// recorded in the sequencer set stored for it.
+pub fn check_leader(
+    block_metadata: &Lock<BlockMetadata>,
+    rollup_id: &RollupId,
+    order_commitment: &OrderCommitment,
+) -> Result<(), Error> {
+    let sequencer_set: SequencerSet = block_metadata.get_for_update(&(
+        "sequencer_set",
+        rollup_id,
+        order_commitment.block_height(),
+    ))?;
+    match sequencer_set.is_leader_address(order_commitment.signer()) {
+        true => Ok(()),
+        false => Err(Error::new(
+            ErrorCode::InvalidSignature,
+            format!(
+                "{:?} is not a leader of {:?}",
+                order_commitment.signer(),
+                order_commitment.block_height(),
+            ),
+        )),
+    }
+}
+
+/// Acknowledge `sync` again if the same transaction is stored at its position, which
+/// happens when the leader retries after a lost acknowledgement.
+fn check_stored(database: &Database, sync: &SyncTransaction) -> Result<(), Error> {
+    let raw_tx: Option<RawTransaction> = match database.get(&(
+        "raw_tx",
+        &sync.rollup_id,
+        &sync.block_height,
+        &sync.tx_order,
+    )) {
+        Ok(raw_tx) => Some(raw_tx),
+        Err(error) if error.is_none_type() => None,
+        Err(error) => return Err(error),
+    };
+
+    match raw_tx {
+        Some(raw_tx) if raw_tx == sync.raw_tx => Ok(()),
+        Some(_) => Err(Error::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "Another transaction is stored at {:?}: {:?}",
+                sync.block_height, sync.tx_order,
+            ),
+        )),
+        None => Err(Error::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "{:?}: {:?} is too far ahead of or behind the follower",
+                sync.block_height, sync.tx_order,
+            ),
+        )),
+    }
+}
@@ -1,5 +1,6 @@
+use ssal_api::ssal::{RegisterSequencer, SubmitBlockCommitment};
 use ssal_core::{
-    reqwest::Url,
+    error::{Error, ErrorCode, WrapError},
     tokio::{
         self,
         time::{sleep, Duration},
@@ -7,157 +8,437 @@ use ssal_core::{
     tracing,
     types::*,
 };
+use ssal_database::Lock;
 
 use crate::{
     app_state::AppState,
-    chain::send_block_commitment,
-    request::{get_closed_sequencer_set, register},
+    build_queue::{self, BuildJob, BuildState},
+    chain::{send_block_commitment, sign_digest},
+    mempool,
+    resync::{catch_up, resync},
+    sync,
 };
 
-pub fn registerer(state: AppState, ssal_url: Url, rollup_id: RollupId, sequencer_id: SequencerId) {
+pub fn registerer(state: AppState, rollup_id: RollupId, sequencer_id: SequencerId, stake: Stake) {
     tokio::spawn(async move {
+        // Blocks are committed to with the commitment method the rollup was registered with.
         loop {
-            if let Some(block_height) = register(&ssal_url, &rollup_id, &sequencer_id)
-                .await
-                .unwrap()
-            {
-                tracing::info!(
-                    "[RegisterSequencer]: Successfully registered for {:?}: {:?}",
-                    &rollup_id,
-                    &block_height,
-                );
-                leader_poller(
-                    state.clone(),
-                    ssal_url.clone(),
-                    rollup_id.clone(),
-                    sequencer_id.clone(),
-                    block_height.clone(),
-                );
+            match state.ssal_client().get_rollup_config(&rollup_id).await {
+                Ok(rollup_config) => match state
+                    .commitment_params()
+                    .load(rollup_config.curve())
+                    .map_err(Error::from)
+                    .and_then(|_| {
+                        state
+                            .database()
+                            .put(&("rollup_config", &rollup_id), &rollup_config)
+                    }) {
+                    Ok(()) => break,
+                    Err(error) => tracing::error!("{}", error),
+                },
+                Err(error) => tracing::error!("{}", error),
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+
+        // Recover what was missed while the sequencer was down, then resume the blocks
+        // left unfinished by the last run.
+        if let Err(error) = resync(&state, &rollup_id).await {
+            tracing::error!("[Resync]: Failed to resync {:?}: {}", rollup_id, error);
+        }
+        block_builder(state.clone(), rollup_id.clone());
+
+        loop {
+            match register(&state, &rollup_id, &sequencer_id, &stake).await {
+                Ok(block_height) => {
+                    tracing::info!(
+                        "[RegisterSequencer]: Successfully registered for {:?}: {:?}",
+                        &rollup_id,
+                        &block_height,
+                    );
+                    leader_poller(
+                        state.clone(),
+                        rollup_id.clone(),
+                        sequencer_id.clone(),
+                        block_height.clone(),
+                    );
+                }
+                Err(error) if error.code() == ErrorCode::SequencerAlreadyRegistered => {}
+                // Paused rollups and excluded sequencers are retried until they are allowed back.
+                Err(error) => tracing::error!("{}", error),
             }
             sleep(Duration::from_millis(500)).await;
         }
     });
 }
 
+/// Register for the block height which is open for registration. The registration is
+/// signed over that block height, so it is signed again for every block height.
+async fn register(
+    state: &AppState,
+    rollup_id: &RollupId,
+    sequencer_id: &SequencerId,
+    stake: &Stake,
+) -> Result<BlockHeight, Error> {
+    let block_height = state.ssal_client().get_block_height(rollup_id).await?;
+    let signature = sign_digest(
+        state.client(),
+        RegisterSequencer::digest(rollup_id, sequencer_id, stake, &block_height),
+    )
+    .await?;
+    state
+        .ssal_client()
+        .register_sequencer(rollup_id, sequencer_id, stake, &block_height, &signature)
+        .await
+}
+
 pub fn leader_poller(
     state: AppState,
-    ssal_url: Url,
     rollup_id: RollupId,
     sequencer_id: SequencerId,
     block_height: BlockHeight,
 ) {
     tokio::spawn(async move {
         loop {
-            if let Some(sequencer_set) =
-                get_closed_sequencer_set(&ssal_url, &rollup_id, &block_height)
-                    .await
-                    .unwrap()
-            {
-                let block_metadata_key = ("block_metadata", &rollup_id);
-                let sequencer_set_key = ("sequencer_set", &rollup_id, &block_height);
-                match state
-                    .database()
-                    .get_mut::<(&'static str, &RollupId), BlockMetadata>(&block_metadata_key)
+            let sequencer_set = get_closed_sequencer_set(&state, &rollup_id, &block_height).await;
+            if let Some(sequencer_set) = sequencer_set {
+                // Followers fetch the transactions of the ending block height which the
+                // leader gave up syncing to them before the block is enqueued for
+                // building, and retry until a peer answers.
+                if let Err(error) = catch_up(&state, &rollup_id, &(block_height.clone() - 1)).await
                 {
-                    Ok(mut block_metadata) => {
-                        let current_block_height = block_metadata.block_height();
-                        let current_tx_count = block_metadata.tx_count();
-
-                        // Build the current block.
-                        block_builder(
-                            state.clone(),
-                            rollup_id.clone(),
-                            current_block_height,
-                            current_tx_count,
-                            block_metadata.is_leader(),
-                        );
-
-                        // Store the sequencer set.
-                        let leader_id = sequencer_set.leader().unwrap();
-                        state
-                            .database()
-                            .put(&sequencer_set_key, &sequencer_set)
-                            .unwrap();
-
-                        // Update the block metadata.
-                        block_metadata.update(
-                            block_height.clone(),
-                            leader_id == sequencer_id,
-                            leader_id,
-                        );
-                        block_metadata.commit().unwrap();
-                    }
-                    Err(error) => {
-                        if error.is_none_type() {
-                            // Store the sequencer set.
-                            let leader_id = sequencer_set.leader().unwrap();
-                            state
-                                .database()
-                                .put(&sequencer_set_key, &sequencer_set)
-                                .unwrap();
-
-                            // Store the block metadata.
-                            let block_metadata = BlockMetadata::new(
-                                block_height.clone(),
-                                leader_id == sequencer_id,
-                                leader_id,
-                            );
-                            state
-                                .database()
-                                .put(&block_metadata_key, &block_metadata)
-                                .unwrap();
+                    tracing::error!("{}", error);
+                    sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+                match start_block_height(
+                    &state,
+                    &rollup_id,
+                    &sequencer_id,
+                    &block_height,
+                    &sequencer_set,
+                ) {
+                    Ok(()) => {
+                        // Followers fetch the transactions they missed before they got here.
+                        if let Err(error) = catch_up(&state, &rollup_id, &block_height).await {
+                            tracing::error!("{}", error);
                         }
+                        leader_watcher(state, rollup_id, block_height);
+                        break;
                     }
+                    Err(error) => tracing::error!("{}", error),
                 }
-                break;
             }
             sleep(Duration::from_millis(100)).await;
         }
     });
 }
 
-pub fn block_builder(
-    state: AppState,
-    rollup_id: RollupId,
-    block_height: BlockHeight,
-    tx_count: TransactionOrder,
-    is_leader: bool,
-) {
+/// Store the sequencer set of `block_height` and move the block metadata to it. The
+/// block of the previous block height is enqueued for `block_builder` in the same
+/// transaction, so that it is built even if the sequencer restarts right after.
+pub fn start_block_height(
+    state: &AppState,
+    rollup_id: &RollupId,
+    sequencer_id: &SequencerId,
+    block_height: &BlockHeight,
+    sequencer_set: &SequencerSet,
+) -> Result<(), Error> {
+    let leader_id = sequencer_set
+        .leader()
+        .wrap(format!("The sequencer set of {:?} is empty", block_height))?;
+    let is_leader = leader_id == *sequencer_id;
+    let block_metadata_key = ("block_metadata", rollup_id);
+    let sequencer_set_key = ("sequencer_set", rollup_id, block_height);
+
+    match state
+        .database()
+        .get_mut::<_, BlockMetadata>(&block_metadata_key)
+    {
+        Ok(mut block_metadata) => {
+            let build_job = BuildJob::new(
+                block_metadata.block_height(),
+                block_metadata.tx_count(),
+                block_metadata.is_leader(),
+            );
+            build_queue::push(&block_metadata, rollup_id, &build_job)?;
+            block_metadata.put(&sequencer_set_key, sequencer_set)?;
+            block_metadata.update(block_height.clone(), is_leader, leader_id);
+
+            // Store the transactions synced before the sequencer got here.
+            sync::drain(&mut block_metadata, rollup_id)?;
+            block_metadata.commit()
+        }
+        Err(error) if error.is_none_type() => {
+            state.database().put(&sequencer_set_key, sequencer_set)?;
+            state.database().put(
+                &block_metadata_key,
+                &BlockMetadata::new(block_height.clone(), is_leader, leader_id),
+            )
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Follow the closed sequencer set of `block_height` until the next block height
+/// starts, and pick up the replacement leader when the SSAL fails over.
+pub fn leader_watcher(state: AppState, rollup_id: RollupId, block_height: BlockHeight) {
     tokio::spawn(async move {
-        let block: Vec<RawTransaction> = tx_count
-            .iter()
-            .map(|tx_order| {
-                let raw_tx: RawTransaction = state
-                    .database()
-                    .get(&("raw_tx", &rollup_id, &block_height, &tx_order))
-                    .unwrap();
-                raw_tx
-            })
-            .collect();
-        state
-            .database()
-            .put(&("block", &rollup_id, &block_height), &block)
-            .unwrap();
-
-        let block_commitment = ssal_commitment::get_block_commitment(block);
-        state
-            .database()
-            .put(
-                &("block_commitment", &rollup_id, &block_height),
+        let block_metadata_key = ("block_metadata", &rollup_id);
+        loop {
+            sleep(Duration::from_millis(500)).await;
+
+            let block_metadata: BlockMetadata = match state.database().get(&block_metadata_key) {
+                Ok(block_metadata) => block_metadata,
+                Err(_) => continue,
+            };
+            if block_metadata.block_height() != block_height {
+                break;
+            }
+
+            let sequencer_set =
+                match get_closed_sequencer_set(&state, &rollup_id, &block_height).await {
+                    Some(sequencer_set) => sequencer_set,
+                    None => continue,
+                };
+            if let Err(error) = follow_leader(&state, &rollup_id, &block_height, &sequencer_set) {
+                tracing::error!("{}", error);
+            }
+        }
+    });
+}
+
+/// Check with the SSAL that the sequencer still leads `block_height` before it signs or
+/// sends anything as the leader. A leader which has been replaced steps down right away
+/// instead of waiting for `leader_watcher`, so that it never acts alongside its
+/// replacement. Fails while the SSAL is unreachable.
+pub async fn confirm_leader(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+) -> Result<bool, Error> {
+    let sequencer_set = state
+        .ssal_client()
+        .get_closed_sequencer_set(rollup_id, block_height)
+        .await?;
+    let leader_id = sequencer_set
+        .leader()
+        .wrap(format!("The sequencer set of {:?} is empty", block_height))?;
+    if leader_id == *state.sequencer_id() {
+        return Ok(true);
+    }
+    follow_leader(state, rollup_id, block_height, &sequencer_set)?;
+    Ok(false)
+}
+
+/// Store the closed `sequencer_set` after a failover and move the block metadata to its
+/// leader, unless `block_height` has already ended.
+fn follow_leader(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    sequencer_set: &SequencerSet,
+) -> Result<(), Error> {
+    let leader_id = sequencer_set
+        .leader()
+        .wrap(format!("The sequencer set of {:?} is empty", block_height))?;
+    let mut block_metadata: Lock<BlockMetadata> =
+        state.database().get_mut(&("block_metadata", rollup_id))?;
+    if block_metadata.block_height() != *block_height || block_metadata.leader_id() == leader_id {
+        return Ok(());
+    }
+    block_metadata.replace_leader(leader_id == *state.sequencer_id(), leader_id.clone());
+    block_metadata.put(&("sequencer_set", rollup_id, block_height), sequencer_set)?;
+    block_metadata.commit()?;
+
+    tracing::info!(
+        "[LeaderWatcher]: The leader for {:?}: {:?} failed over to {:?}",
+        rollup_id,
+        block_height,
+        leader_id,
+    );
+    Ok(())
+}
+
+/// Returns `None` until the SequencerSet of `block_height` is closed.
+async fn get_closed_sequencer_set(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+) -> Option<SequencerSet> {
+    match state
+        .ssal_client()
+        .get_closed_sequencer_set(rollup_id, block_height)
+        .await
+    {
+        Ok(sequencer_set) => Some(sequencer_set),
+        Err(error) => {
+            if error.code() != ErrorCode::NotFound {
+                tracing::error!("{}", error);
+            }
+            None
+        }
+    }
+}
+
+/// Build the blocks in the build queue of the rollup one at a time, oldest first,
+/// starting with the ones left unfinished by the last run. A failed step is retried
+/// with exponential backoff. The block commitment may be sent twice if the sequencer
+/// restarts between sending it and persisting that it was sent.
+pub fn block_builder(state: AppState, rollup_id: RollupId) {
+    tokio::spawn(async move {
+        let mut backoff = MIN_BUILD_BACKOFF;
+        loop {
+            let mut build_job = match build_queue::front(state.database(), &rollup_id) {
+                Ok(Some(build_job)) => build_job,
+                Ok(None) => {
+                    sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+                Err(error) => {
+                    tracing::error!("{}", error);
+                    sleep(backoff).await;
+                    continue;
+                }
+            };
+
+            match build_step(&state, &rollup_id, &mut build_job).await {
+                Ok(()) => backoff = MIN_BUILD_BACKOFF,
+                Err(error) => {
+                    tracing::error!(
+                        "[BlockBuilder]: Failed to advance {:?}: {:?} from {:?}, retrying in {:?}: {}",
+                        rollup_id,
+                        build_job.block_height(),
+                        build_job.state(),
+                        backoff,
+                        error,
+                    );
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BUILD_BACKOFF);
+                }
+            }
+        }
+    });
+}
+
+const MIN_BUILD_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BUILD_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Sign `block_commitment` and submit it to the SSAL, which mixes it into the election
+/// seed two block heights later.
+async fn submit_block_commitment(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: &BlockHeight,
+    block_commitment: &BlockCommitment,
+) -> Result<(), Error> {
+    let signature = sign_digest(
+        state.client(),
+        SubmitBlockCommitment::digest(rollup_id, block_height, block_commitment),
+    )
+    .await?;
+    state
+        .ssal_client()
+        .submit_block_commitment(
+            rollup_id,
+            block_height,
+            state.sequencer_id(),
+            block_commitment,
+            &signature,
+        )
+        .await
+}
+
+/// Take `build_job` one [`BuildState`] further and persist the progress in the build
+/// queue.
+pub async fn build_step(
+    state: &AppState,
+    rollup_id: &RollupId,
+    build_job: &mut BuildJob,
+) -> Result<(), Error> {
+    let block_height = build_job.block_height().clone();
+    match build_job.state() {
+        BuildState::Pending => {
+            let block = build_job
+                .tx_count()
+                .iter()
+                .map(|tx_order| {
+                    state
+                        .database()
+                        .get(&("raw_tx", rollup_id, &block_height, &tx_order))
+                })
+                .collect::<Result<Vec<RawTransaction>, Error>>()?;
+            state
+                .database()
+                .put(&("block", rollup_id, &block_height), &block)?;
+            build_queue::advance(state.database(), rollup_id, build_job, BuildState::Built)
+        }
+        BuildState::Built => {
+            let block: Vec<RawTransaction> =
+                state.database().get(&("block", rollup_id, &block_height))?;
+            let rollup_config: RollupConfig =
+                state.database().get(&("rollup_config", rollup_id))?;
+            let block_commitment = ssal_commitment::get_block_commitment(
+                state.commitment_params(),
+                rollup_config.curve(),
+                rollup_config.commitment_method(),
+                &block,
+            )?;
+            state.database().put(
+                &("block_commitment", rollup_id, &block_height),
                 &block_commitment,
+            )?;
+
+            // Transactions leaving the dedup window may be sequenced again.
+            mempool::prune(
+                state.database(),
+                rollup_id,
+                &block_height,
+                state.dedup_window(),
+            )?;
+            build_queue::advance(
+                state.database(),
+                rollup_id,
+                build_job,
+                BuildState::Committed,
             )
-            .unwrap();
+        }
+        BuildState::Committed => {
+            let block_commitment: BlockCommitment =
+                state
+                    .database()
+                    .get(&("block_commitment", rollup_id, &block_height))?;
+
+            // A leader which has been replaced leaves the contract to its replacement.
+            let is_leader =
+                build_job.is_leader() && confirm_leader(state, rollup_id, &block_height).await?;
 
-        if is_leader {
-            send_block_commitment(state.client(), &rollup_id, &block_height, &block_commitment)
-                .await
-                .unwrap();
+            // Followers submit the commitment too, so that a leader cannot keep it out of
+            // the election seed by withholding it. An election without it is still valid,
+            // so a failed submission does not hold the block back.
+            if let Err(error) =
+                submit_block_commitment(state, rollup_id, &block_height, &block_commitment).await
+            {
+                tracing::error!("{}", error);
+            }
+            if is_leader {
+                send_block_commitment(state.client(), rollup_id, &block_height, &block_commitment)
+                    .await?;
 
-            tracing::info!(
-                "[Leader]: Successfully sent block commitment to the contract for {:?}: {:?}",
+                tracing::info!(
+                    "[Leader]: Successfully sent block commitment to the contract for {:?}: {:?}",
+                    rollup_id,
+                    block_height,
+                );
+            }
+            build_queue::advance(
+                state.database(),
                 rollup_id,
-                block_height,
-            );
+                build_job,
+                BuildState::Submitted,
+            )
         }
-    });
+        // Submitted blocks leave the queue.
+        BuildState::Submitted => Ok(()),
+    }
 }
@@ -0,0 +1,170 @@
+mod common;
+
+use ssal_core::{tokio, types::*};
+use ssal_database::Lock;
+use ssal_sequencer::{
+    app_state::AppState,
+    build_queue::{self, BuildJob, BuildState},
+    task,
+};
+
+/// Sequence `raw_txs` at the current block height as the leader would.
+fn sequence(state: &AppState, rollup_id: &RollupId, raw_txs: &[&str]) {
+    let mut block_metadata: Lock<BlockMetadata> = state
+        .database()
+        .get_mut(&("block_metadata", rollup_id))
+        .unwrap();
+    let block_height = block_metadata.block_height();
+    for raw_tx in raw_txs {
+        let tx_order = block_metadata.issue_tx_order();
+        block_metadata
+            .put(
+                &("raw_tx", rollup_id, &block_height, &tx_order),
+                &RawTransaction::from(raw_tx.to_string()),
+            )
+            .unwrap();
+    }
+    block_metadata.commit().unwrap();
+}
+
+fn front(state: &AppState, rollup_id: &RollupId) -> Option<BuildJob> {
+    build_queue::front(state.database(), rollup_id).unwrap()
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn followers_build_and_commit_every_ended_block() {
+    let (_database_dir, state) = common::app_state("127.0.0.1:8000").await;
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id);
+
+    // The first block height has nothing to build before it.
+    common::start_block_height(&state, &rollup_id, 1, "127.0.0.1:8001");
+    assert!(front(&state, &rollup_id).is_none());
+    sequence(&state, &rollup_id, &["a", "b"]);
+
+    common::start_block_height(&state, &rollup_id, 2, "127.0.0.1:8001");
+    let mut build_job = front(&state, &rollup_id).unwrap();
+    assert_eq!(*build_job.block_height(), 1);
+    assert_eq!(*build_job.tx_count(), TransactionOrder::from(2));
+    assert!(!build_job.is_leader());
+    assert_eq!(build_job.state(), BuildState::Pending);
+
+    task::build_step(&state, &rollup_id, &mut build_job)
+        .await
+        .unwrap();
+    let mut build_job = front(&state, &rollup_id).unwrap();
+    assert_eq!(build_job.state(), BuildState::Built);
+    let block: Vec<RawTransaction> = state
+        .database()
+        .get(&("block", &rollup_id, &BlockHeight::from(1)))
+        .unwrap();
+    assert_eq!(
+        block.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        ["a", "b"],
+    );
+
+    task::build_step(&state, &rollup_id, &mut build_job)
+        .await
+        .unwrap();
+    let mut build_job = front(&state, &rollup_id).unwrap();
+    assert_eq!(build_job.state(), BuildState::Committed);
+    let block_commitment: BlockCommitment = state
+        .database()
+        .get(&("block_commitment", &rollup_id, &BlockHeight::from(1)))
+        .unwrap();
+    assert_eq!(
+        block_commitment.commitment_method(),
+        CommitmentMethod::MerkleSha256
+    );
+
+    // Followers do not send the commitment, so the block leaves the queue right away.
+    task::build_step(&state, &rollup_id, &mut build_job)
+        .await
+        .unwrap();
+    assert!(front(&state, &rollup_id).is_none());
+
+    // A submitted block is never enqueued again.
+    let block_metadata: Lock<BlockMetadata> = state
+        .database()
+        .get_mut(&("block_metadata", &rollup_id))
+        .unwrap();
+    let build_job = BuildJob::new(1.into(), TransactionOrder::default(), false);
+    build_queue::push(&block_metadata, &rollup_id, &build_job).unwrap();
+    block_metadata.commit().unwrap();
+    assert!(build_queue::unfinished(state.database(), &rollup_id)
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn the_leader_keeps_a_committed_block_until_it_is_sent() {
+    let (_database_dir, state) = common::app_state("127.0.0.1:8000").await;
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id);
+
+    common::start_block_height(&state, &rollup_id, 1, "127.0.0.1:8000");
+    sequence(&state, &rollup_id, &["a"]);
+    common::start_block_height(&state, &rollup_id, 2, "127.0.0.1:8000");
+
+    let mut build_job = front(&state, &rollup_id).unwrap();
+    assert!(build_job.is_leader());
+    while build_job.state() != BuildState::Committed {
+        task::build_step(&state, &rollup_id, &mut build_job)
+            .await
+            .unwrap();
+        build_job = front(&state, &rollup_id).unwrap();
+    }
+
+    // Neither the SSAL nor the chain is reachable, so the step is retried later.
+    assert!(task::build_step(&state, &rollup_id, &mut build_job)
+        .await
+        .is_err());
+    let build_job = front(&state, &rollup_id).unwrap();
+    assert_eq!(*build_job.block_height(), 1);
+    assert_eq!(build_job.state(), BuildState::Committed);
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_replaced_leader_steps_down_before_it_sends() {
+    let rollup_id = RollupId::from("1");
+    let ssal_url = common::ssal(common::sequencer_set(&rollup_id, 1, "127.0.0.1:8001")).await;
+    let (_database_dir, state) = common::app_state_with_ssal("127.0.0.1:8000", &ssal_url).await;
+    common::register_rollup(&state, &rollup_id);
+
+    common::start_block_height(&state, &rollup_id, 1, "127.0.0.1:8000");
+    assert!(!task::confirm_leader(&state, &rollup_id, &1.into())
+        .await
+        .unwrap());
+    let block_metadata: BlockMetadata = state
+        .database()
+        .get(&("block_metadata", &rollup_id))
+        .unwrap();
+    assert!(!block_metadata.is_leader());
+    assert_eq!(
+        block_metadata.leader_id(),
+        SequencerId::from("127.0.0.1:8001")
+    );
+
+    // The block was sequenced before the failover, so it is still enqueued as the
+    // leader's, but its commitment is left to the replacement.
+    sequence(&state, &rollup_id, &["a"]);
+    let mut block_metadata: Lock<BlockMetadata> = state
+        .database()
+        .get_mut(&("block_metadata", &rollup_id))
+        .unwrap();
+    let build_job = BuildJob::new(1.into(), block_metadata.tx_count(), true);
+    block_metadata.update(2.into(), false, "127.0.0.1:8001".into());
+    build_queue::push(&block_metadata, &rollup_id, &build_job).unwrap();
+    block_metadata.commit().unwrap();
+
+    let mut build_job = front(&state, &rollup_id).unwrap();
+    assert!(build_job.is_leader());
+    while front(&state, &rollup_id).is_some() {
+        task::build_step(&state, &rollup_id, &mut build_job)
+            .await
+            .unwrap();
+        if let Some(next) = front(&state, &rollup_id) {
+            build_job = next;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// Each test crate uses a different part of the helpers.
+#![allow(dead_code)]
+
+use ethers::signers::LocalWallet;
+use ssal_api::{
+    sequencer::{CommittedBlock, GetBlocks},
+    ssal::{GetClosedSequencerSet, GetEndorsedBlockCommitment},
+    Endpoint, HttpClient, SsalClient,
+};
+use ssal_commitment::srs::CommitmentParamSet;
+use ssal_core::{
+    axum::{routing::get, Json, Router},
+    election::*,
+    reqwest::Url,
+    tokio::{self, net::TcpListener},
+    types::*,
+};
+use ssal_database::Database;
+use ssal_sequencer::{
+    app_state::AppState, chain::init_client, mempool::DEFAULT_DEDUP_WINDOW, task,
+};
+use tempfile::TempDir;
+
+/// Nothing listens here, so every request to the SSAL or the chain fails.
+pub const UNREACHABLE_URL: &str = "http://127.0.0.1:1";
+
+/// A database which is removed with the returned directory.
+pub fn database() -> (TempDir, Database) {
+    let database_dir = TempDir::new().unwrap();
+    let database = Database::new(database_dir.path()).unwrap();
+    (database_dir, database)
+}
+
+/// The state of the sequencer `sequencer_id` on a fresh database, with a new wallet
+/// and without a reachable SSAL or chain.
+pub async fn app_state(sequencer_id: &str) -> (TempDir, AppState) {
+    app_state_with_ssal(sequencer_id, UNREACHABLE_URL).await
+}
+
+/// Like [`app_state`], with the SSAL at `ssal_url`.
+pub async fn app_state_with_ssal(sequencer_id: &str, ssal_url: &str) -> (TempDir, AppState) {
+    let (database_dir, database) = database();
+    let wallet = LocalWallet::new(&mut ssal_core::rand::thread_rng());
+    let private_key = ssal_core::ethers_core::utils::hex::encode(wallet.signer().to_bytes());
+    let client = init_client(UNREACHABLE_URL, private_key).await.unwrap();
+    let http_client = HttpClient::new(HttpClient::DEFAULT_TIMEOUT).unwrap();
+    let ssal_client = SsalClient::new(Url::parse(ssal_url).unwrap(), http_client.clone());
+    let app_state = AppState::new(
+        client,
+        database,
+        http_client,
+        ssal_client,
+        sequencer_id.into(),
+        DEFAULT_DEDUP_WINDOW,
+        CommitmentParamSet::new(database_dir.path().join("srs")),
+    );
+    (database_dir, app_state)
+}
+
+/// Serve `sequencer_set` as the closed sequencer set of every block height, and return
+/// the URL of the server.
+pub async fn ssal(sequencer_set: SequencerSet) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    serve(listener, closed_sequencer_set_router(sequencer_set)).await
+}
+
+/// Like [`ssal`], also serving `endorsed_block_commitment` as the final block commitment
+/// of every block height.
+pub async fn ssal_with_block_commitment(
+    sequencer_set: SequencerSet,
+    endorsed_block_commitment: EndorsedBlockCommitment,
+) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let app = closed_sequencer_set_router(sequencer_set).route(
+        GetEndorsedBlockCommitment::PATH,
+        get(move || async move { Json(endorsed_block_commitment) }),
+    );
+    serve(listener, app).await
+}
+
+/// The listener of a stub sequencer and the sequencer ID of its address, so that it can
+/// join a sequencer set before it serves anything.
+pub async fn peer_listener() -> (TcpListener, SequencerId) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let sequencer_id = listener.local_addr().unwrap().to_string().into();
+    (listener, sequencer_id)
+}
+
+/// Serve `committed_block` for every range of block heights on `listener`.
+pub async fn serve_block(listener: TcpListener, committed_block: CommittedBlock) {
+    let app = Router::new().route(
+        GetBlocks::PATH,
+        get(move || async move { Json(vec![committed_block]) }),
+    );
+    serve(listener, app).await;
+}
+
+fn closed_sequencer_set_router(sequencer_set: SequencerSet) -> Router {
+    Router::new().route(
+        GetClosedSequencerSet::PATH,
+        get(move || async move { Json(sequencer_set) }),
+    )
+}
+
+async fn serve(listener: TcpListener, app: Router) -> String {
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { ssal_core::axum::serve(listener, app).await });
+    format!("http://{}", address)
+}
+
+/// A closed sequencer set whose only candidate, and so its leader, is `leader_id`.
+pub fn sequencer_set(rollup_id: &RollupId, block_height: usize, leader_id: &str) -> SequencerSet {
+    let mut sequencer_set = SequencerSet::new(block_height.into());
+    sequencer_set
+        .register(leader_id.into(), Default::default(), Stake::default())
+        .unwrap();
+    sequencer_set
+        .elect_leader(
+            rollup_id,
+            ElectionSeed::genesis(rollup_id),
+            None,
+            ElectionMethod::Random,
+        )
+        .unwrap();
+    sequencer_set
+}
+
+pub fn start_block_height(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: usize,
+    leader_id: &str,
+) {
+    task::start_block_height(
+        state,
+        rollup_id,
+        state.sequencer_id(),
+        &block_height.into(),
+        &sequencer_set(rollup_id, block_height, leader_id),
+    )
+    .unwrap();
+}
+
+/// Register the rollup with Merkle tree commitments, which need no SRS.
+pub fn register_rollup(state: &AppState, rollup_id: &RollupId) {
+    let rollup_config = RollupConfig::new(
+        ElectionMethod::Random,
+        CommitmentMethod::MerkleSha256,
+        Curve::default(),
+        DurabilityMode::default(),
+    );
+    state
+        .database()
+        .put(&("rollup_config", rollup_id), &rollup_config)
+        .unwrap();
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use ssal_api::sequencer::{SendTransaction, SendTransactions};
+use ssal_core::{
+    axum::{body::to_bytes, extract::State, response::IntoResponse, Json},
+    serde::de::DeserializeOwned,
+    serde_json, tokio,
+    types::*,
+};
+use ssal_sequencer::{app_state::AppState, interface::*, mempool::DEFAULT_DEDUP_WINDOW};
+use tempfile::TempDir;
+
+const LEADER_ID: &str = "127.0.0.1:8000";
+
+/// The state of the only sequencer of the rollup, which leads every block height.
+async fn leader(rollup_id: &RollupId) -> (TempDir, AppState) {
+    let ssal_url = common::ssal(common::sequencer_set(rollup_id, 1, LEADER_ID)).await;
+    let (database_dir, state) = common::app_state_with_ssal(LEADER_ID, &ssal_url).await;
+    common::register_rollup(&state, rollup_id);
+    (database_dir, state)
+}
+
+async fn body<T: DeserializeOwned>(response: impl IntoResponse) -> T {
+    let body = to_bytes(response.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn send(state: &AppState, rollup_id: &RollupId, raw_tx: &str) -> OrderCommitment {
+    let payload = SendTransaction {
+        rollup_id: rollup_id.clone(),
+        raw_tx: raw_tx.into(),
+        forwarded: false,
+    };
+    let response = send_transaction::handler(State(state.clone()), Json(payload))
+        .await
+        .unwrap();
+    body(response).await
+}
+
+fn position(order_commitment: &OrderCommitment) -> (usize, TransactionOrder) {
+    (
+        order_commitment.block_height().value(),
+        order_commitment.tx_order().clone(),
+    )
+}
+
+fn tx_count(state: &AppState, rollup_id: &RollupId) -> TransactionOrder {
+    let block_metadata: BlockMetadata = state
+        .database()
+        .get(&("block_metadata", rollup_id))
+        .unwrap();
+    block_metadata.tx_count()
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn resubmissions_within_the_window_keep_their_order_commitment() {
+    let rollup_id = RollupId::from("1");
+    let (_database_dir, state) = leader(&rollup_id).await;
+    common::start_block_height(&state, &rollup_id, 1, LEADER_ID);
+    let original = send(&state, &rollup_id, "a").await;
+    assert_eq!(position(&original), (1, TransactionOrder::from(0)));
+
+    for block_height in [2, DEFAULT_DEDUP_WINDOW] {
+        common::start_block_height(&state, &rollup_id, block_height, LEADER_ID);
+        let resubmitted = send(&state, &rollup_id, "a").await;
+        assert_eq!(position(&resubmitted), position(&original));
+        assert_eq!(resubmitted.signature(), original.signature());
+        assert_eq!(tx_count(&state, &rollup_id), TransactionOrder::default());
+    }
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn transactions_are_sequenced_again_after_the_window() {
+    let rollup_id = RollupId::from("1");
+    let (_database_dir, state) = leader(&rollup_id).await;
+    common::start_block_height(&state, &rollup_id, 1, LEADER_ID);
+    send(&state, &rollup_id, "a").await;
+
+    let block_height = 1 + DEFAULT_DEDUP_WINDOW;
+    common::start_block_height(&state, &rollup_id, block_height, LEADER_ID);
+    let sequenced_again = send(&state, &rollup_id, "a").await;
+    assert_eq!(
+        position(&sequenced_again),
+        (block_height, TransactionOrder::from(0))
+    );
+
+    // The new position is remembered from now on.
+    let resubmitted = send(&state, &rollup_id, "a").await;
+    assert_eq!(position(&resubmitted), position(&sequenced_again));
+    assert_eq!(tx_count(&state, &rollup_id), TransactionOrder::from(1));
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn duplicates_within_a_batch_are_sequenced_once() {
+    let rollup_id = RollupId::from("1");
+    let (_database_dir, state) = leader(&rollup_id).await;
+    common::start_block_height(&state, &rollup_id, 1, LEADER_ID);
+
+    let payload = SendTransactions {
+        rollup_id: rollup_id.clone(),
+        raw_txs: ["a", "b", "a", "c", "b"]
+            .into_iter()
+            .map(RawTransaction::from)
+            .collect(),
+        forwarded: false,
+    };
+    let response = send_transactions::handler(State(state.clone()), Json(payload))
+        .await
+        .unwrap();
+    let order_commitments: Vec<OrderCommitment> = body(response).await;
+    let tx_orders: Vec<usize> = order_commitments
+        .iter()
+        .map(|order_commitment| order_commitment.tx_order().value())
+        .collect();
+    assert_eq!(tx_orders, [0, 1, 0, 2, 1]);
+    assert_eq!(tx_count(&state, &rollup_id), TransactionOrder::from(3));
+}
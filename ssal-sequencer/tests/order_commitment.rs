@@ -0,0 +1,40 @@
+mod common;
+
+use ssal_core::{error::ErrorCode, ethers_core::types::Address, tokio, types::*};
+use ssal_sequencer::chain::sign_order_commitment;
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn order_commitments_only_verify_for_their_signer_and_transaction() {
+    let (_database_dir, state) = common::app_state("127.0.0.1:8000").await;
+    let rollup_id = RollupId::from("1");
+    let raw_tx = RawTransaction::from("a");
+    let order_commitment = sign_order_commitment(
+        state.client(),
+        &rollup_id,
+        1.into(),
+        TransactionOrder::from(0),
+        &raw_tx,
+    )
+    .await
+    .unwrap();
+    order_commitment.verify(&rollup_id, &raw_tx).unwrap();
+
+    let error = order_commitment
+        .verify(&rollup_id, &RawTransaction::from("b"))
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidSignature);
+    let error = order_commitment
+        .verify(&RollupId::from("2"), &raw_tx)
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidSignature);
+
+    let wrong_signer = OrderCommitment::new(
+        order_commitment.block_height().clone(),
+        order_commitment.tx_order().clone(),
+        order_commitment.tx_hash().clone(),
+        *order_commitment.signature(),
+        Address::random(),
+    );
+    let error = wrong_signer.verify(&rollup_id, &raw_tx).unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidSignature);
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use ethers::signers::{LocalWallet, Signer};
+use ssal_api::{sequencer::CommittedBlock, ssal::SubmitBlockCommitment};
+use ssal_commitment::srs::CommitmentParamSet;
+use ssal_core::{election::*, tokio, types::*};
+use ssal_sequencer::{
+    app_state::AppState,
+    build_queue::{self, BuildState},
+    resync, task,
+};
+use tempfile::TempDir;
+
+const FOLLOWER_ID: &str = "127.0.0.1:8001";
+
+fn block() -> Vec<RawTransaction> {
+    (0..4)
+        .map(|index| RawTransaction::from(format!("transaction {}", index)))
+        .collect()
+}
+
+/// The Merkle tree commitment of `block`, which needs no SRS.
+fn block_commitment(block: &[RawTransaction]) -> BlockCommitment {
+    ssal_commitment::get_block_commitment(
+        &CommitmentParamSet::new(std::env::temp_dir()),
+        Curve::default(),
+        CommitmentMethod::MerkleSha256,
+        block,
+    )
+    .unwrap()
+}
+
+/// Resync a follower which missed the end of block height 1. The leader of block height 1
+/// is a peer serving `served_block` with the commitment of [`block`], which the SSAL
+/// serves as endorsed by the leader with a signature from `signer`.
+async fn resync_follower(
+    rollup_id: &RollupId,
+    served_block: Vec<RawTransaction>,
+    signer: Option<LocalWallet>,
+) -> (TempDir, AppState) {
+    let block_height = BlockHeight::from(1);
+    let leader_wallet = LocalWallet::new(&mut ssal_core::rand::thread_rng());
+    let signer = signer.unwrap_or_else(|| leader_wallet.clone());
+    let (listener, leader_id) = common::peer_listener().await;
+    let mut sequencer_set = SequencerSet::new(block_height.clone());
+    sequencer_set
+        .register(leader_id.clone(), leader_wallet.address(), Stake::default())
+        .unwrap();
+    sequencer_set
+        .elect_leader(
+            rollup_id,
+            ElectionSeed::genesis(rollup_id),
+            None,
+            ElectionMethod::Random,
+        )
+        .unwrap();
+
+    let block_commitment = block_commitment(&block());
+    let digest = SubmitBlockCommitment::digest(rollup_id, &block_height, &block_commitment);
+    let mut endorsed_block_commitment = EndorsedBlockCommitment::new(block_commitment.clone());
+    endorsed_block_commitment.endorse(leader_id, signer.sign_message(digest).await.unwrap());
+    tokio::spawn(common::serve_block(
+        listener,
+        CommittedBlock {
+            block_height: block_height.clone(),
+            block: served_block,
+            block_commitment,
+        },
+    ));
+
+    // The SSAL serves the same closed set for block height 2, so block height 1 has ended.
+    let ssal_url =
+        common::ssal_with_block_commitment(sequencer_set.clone(), endorsed_block_commitment).await;
+    let (database_dir, state) = common::app_state_with_ssal(FOLLOWER_ID, &ssal_url).await;
+    common::register_rollup(&state, rollup_id);
+    task::start_block_height(
+        &state,
+        rollup_id,
+        state.sequencer_id(),
+        &block_height,
+        &sequencer_set,
+    )
+    .unwrap();
+    resync::resync(&state, rollup_id).await.unwrap();
+    (database_dir, state)
+}
+
+fn recovered_block(state: &AppState, rollup_id: &RollupId) -> Option<Vec<RawTransaction>> {
+    match state
+        .database()
+        .get(&("block", rollup_id, &BlockHeight::from(1)))
+    {
+        Ok(block) => Some(block),
+        Err(error) if error.is_none_type() => None,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+fn build_state(state: &AppState, rollup_id: &RollupId) -> BuildState {
+    build_queue::unfinished(state.database(), rollup_id).unwrap()[0].state()
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_block_matching_the_endorsed_commitment_is_recovered() {
+    let rollup_id = RollupId::from("1");
+    let (_database_dir, state) = resync_follower(&rollup_id, block(), None).await;
+
+    assert_eq!(recovered_block(&state, &rollup_id), Some(block()));
+    assert_eq!(build_state(&state, &rollup_id), BuildState::Committed);
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_tampered_block_from_a_peer_is_not_recovered() {
+    let rollup_id = RollupId::from("1");
+    let mut tampered = block();
+    tampered[2] = RawTransaction::from("tampered transaction");
+    let (_database_dir, state) = resync_follower(&rollup_id, tampered, None).await;
+
+    // The block is left to be built from the stored transactions instead.
+    assert_eq!(recovered_block(&state, &rollup_id), None);
+    assert_eq!(build_state(&state, &rollup_id), BuildState::Pending);
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_block_commitment_without_the_leader_signature_is_not_trusted() {
+    let rollup_id = RollupId::from("1");
+    let forger = LocalWallet::new(&mut ssal_core::rand::thread_rng());
+    let (_database_dir, state) = resync_follower(&rollup_id, block(), Some(forger)).await;
+
+    assert_eq!(recovered_block(&state, &rollup_id), None);
+    assert_eq!(build_state(&state, &rollup_id), BuildState::Pending);
+}
@@ -0,0 +1,265 @@
+mod common;
+
+use ethers::types::{Address, Signature, U256};
+use ssal_api::sequencer::{SequencedTransaction, SyncTransaction};
+use ssal_core::{election::*, types::*};
+use ssal_database::{Database, Lock};
+use ssal_sequencer::sync;
+
+/// A synced transaction whose order commitment is not checked by the follower state.
+fn sync_transaction(rollup_id: &RollupId, block_height: usize, tx_order: usize) -> SyncTransaction {
+    let raw_tx = RawTransaction::from(format!("{}-{}", block_height, tx_order));
+    let signature = Signature {
+        r: U256::zero(),
+        s: U256::zero(),
+        v: 0,
+    };
+    SyncTransaction {
+        rollup_id: rollup_id.clone(),
+        block_height: block_height.into(),
+        tx_order: tx_order.into(),
+        order_commitment: OrderCommitment::new(
+            block_height.into(),
+            tx_order.into(),
+            raw_tx.hash(),
+            signature,
+            Address::zero(),
+        ),
+        raw_tx,
+    }
+}
+
+/// Follow the leader from block height 1, with the sequencer sets of the first block
+/// heights stored as `start_block_height` would.
+fn follow(database: &Database, rollup_id: &RollupId) {
+    database
+        .put(
+            &("block_metadata", rollup_id),
+            &BlockMetadata::new(1.into(), false, "leader".into()),
+        )
+        .unwrap();
+    for block_height in 1..=3 {
+        let mut sequencer_set = SequencerSet::new(block_height.into());
+        sequencer_set
+            .register("leader".into(), Address::zero(), Stake::default())
+            .unwrap();
+        sequencer_set
+            .elect_leader(
+                rollup_id,
+                ElectionSeed::genesis(rollup_id),
+                None,
+                ElectionMethod::RoundRobin,
+            )
+            .unwrap();
+        database
+            .put(
+                &("sequencer_set", rollup_id, &BlockHeight::from(block_height)),
+                &sequencer_set,
+            )
+            .unwrap();
+    }
+}
+
+fn tx_count(database: &Database, rollup_id: &RollupId) -> TransactionOrder {
+    let block_metadata: BlockMetadata = database.get(&("block_metadata", rollup_id)).unwrap();
+    block_metadata.tx_count()
+}
+
+fn receive(database: &Database, sync: &SyncTransaction) -> bool {
+    sync::receive(database, sync).is_ok()
+}
+
+fn stored(
+    database: &Database,
+    rollup_id: &RollupId,
+    block_height: usize,
+    tx_order: usize,
+) -> String {
+    let raw_tx: RawTransaction = database
+        .get(&(
+            "raw_tx",
+            rollup_id,
+            &BlockHeight::from(block_height),
+            &TransactionOrder::from(tx_order),
+        ))
+        .unwrap();
+    raw_tx.to_string()
+}
+
+#[test]
+fn followers_keep_the_order_of_the_leader() {
+    let (_database_dir, database) = common::database();
+    let rollup_id = RollupId::from("1");
+    follow(&database, &rollup_id);
+
+    // Out-of-order arrivals wait for the gap to be filled.
+    assert!(receive(&database, &sync_transaction(&rollup_id, 1, 2)));
+    assert!(receive(&database, &sync_transaction(&rollup_id, 1, 1)));
+    assert!(receive(&database, &sync_transaction(&rollup_id, 2, 0)));
+    let block_metadata: BlockMetadata = database.get(&("block_metadata", &rollup_id)).unwrap();
+    assert_eq!(block_metadata.tx_count(), TransactionOrder::from(0));
+
+    assert!(receive(&database, &sync_transaction(&rollup_id, 1, 0)));
+    let block_metadata: BlockMetadata = database.get(&("block_metadata", &rollup_id)).unwrap();
+    assert_eq!(block_metadata.tx_count(), TransactionOrder::from(3));
+    for tx_order in 0..3 {
+        assert_eq!(
+            stored(&database, &rollup_id, 1, tx_order),
+            format!("1-{}", tx_order),
+        );
+    }
+
+    // Retries are acknowledged, while conflicting and far-ahead transactions are not.
+    assert!(receive(&database, &sync_transaction(&rollup_id, 1, 1)));
+    let mut conflicting = sync_transaction(&rollup_id, 1, 1);
+    conflicting.raw_tx = RawTransaction::from("conflicting");
+    assert!(!receive(&database, &conflicting));
+    assert!(!receive(
+        &database,
+        &sync_transaction(&rollup_id, 1, 3 + sync::MAX_BUFFERED_SYNCS + 1),
+    ));
+    assert!(!receive(&database, &sync_transaction(&rollup_id, 3, 0)));
+
+    assert!(receive(&database, &sync_transaction(&rollup_id, 1, 5)));
+
+    // The next block height starts with the transactions buffered for it, and the ones
+    // left behind by the ended block height are deleted.
+    let mut block_metadata: Lock<BlockMetadata> =
+        database.get_mut(&("block_metadata", &rollup_id)).unwrap();
+    block_metadata.update(2.into(), false, "leader".into());
+    sync::drain(&mut block_metadata, &rollup_id).unwrap();
+    block_metadata.commit().unwrap();
+    let block_metadata: BlockMetadata = database.get(&("block_metadata", &rollup_id)).unwrap();
+    assert_eq!(block_metadata.tx_count(), TransactionOrder::from(1));
+    assert_eq!(stored(&database, &rollup_id, 2, 0), "2-0");
+    let left_behind = database.get::<_, SyncTransaction>(&(
+        "sync_buffer",
+        &rollup_id,
+        &BlockHeight::from(1),
+        &TransactionOrder::from(5),
+    ));
+    assert!(left_behind.unwrap_err().is_none_type());
+    let positions: Vec<(BlockHeight, TransactionOrder)> =
+        database.get(&("sync_buffer_index", &rollup_id)).unwrap();
+    assert!(positions.is_empty());
+}
+
+#[test]
+fn batches_keep_the_transactions_before_a_rejected_one() {
+    let (_database_dir, database) = common::database();
+    let rollup_id = RollupId::from("1");
+    follow(&database, &rollup_id);
+
+    let batch: Vec<SyncTransaction> = (0..3)
+        .map(|tx_order| sync_transaction(&rollup_id, 1, tx_order))
+        .collect();
+    assert!(sync::receive_all(&database, &rollup_id, &batch[..2]).is_ok());
+
+    // A retried batch is acknowledged up to the conflicting transaction.
+    let mut conflicting = sync_transaction(&rollup_id, 1, 2);
+    conflicting.raw_tx = RawTransaction::from("conflicting");
+    let mut retried = batch.clone();
+    retried.push(conflicting);
+    assert!(sync::receive_all(&database, &rollup_id, &retried).is_err());
+    let block_metadata: BlockMetadata = database.get(&("block_metadata", &rollup_id)).unwrap();
+    assert_eq!(block_metadata.tx_count(), TransactionOrder::from(3));
+    assert_eq!(stored(&database, &rollup_id, 1, 2), "1-2");
+}
+
+#[test]
+fn followers_only_store_transactions_signed_by_the_leader() {
+    let (_database_dir, database) = common::database();
+    let rollup_id = RollupId::from("1");
+    follow(&database, &rollup_id);
+    let forge = |mut sync: SyncTransaction| {
+        sync.order_commitment = OrderCommitment::new(
+            sync.block_height.clone(),
+            sync.tx_order.clone(),
+            sync.raw_tx.hash(),
+            *sync.order_commitment.signature(),
+            Address::from_low_u64_be(1),
+        );
+        sync
+    };
+
+    assert!(!receive(
+        &database,
+        &forge(sync_transaction(&rollup_id, 1, 0))
+    ));
+    assert!(!receive(
+        &database,
+        &forge(sync_transaction(&rollup_id, 1, 1))
+    ));
+    assert_eq!(tx_count(&database, &rollup_id), TransactionOrder::from(0));
+
+    // Transactions of the next block height are checked once it starts.
+    assert!(receive(
+        &database,
+        &forge(sync_transaction(&rollup_id, 2, 0))
+    ));
+    assert!(receive(&database, &sync_transaction(&rollup_id, 1, 0)));
+    let mut block_metadata: Lock<BlockMetadata> =
+        database.get_mut(&("block_metadata", &rollup_id)).unwrap();
+    block_metadata.update(2.into(), false, "leader".into());
+    sync::drain(&mut block_metadata, &rollup_id).unwrap();
+    block_metadata.commit().unwrap();
+    assert_eq!(tx_count(&database, &rollup_id), TransactionOrder::from(0));
+
+    // The dropped transaction leaves a gap for the leader to fill.
+    assert!(receive(&database, &sync_transaction(&rollup_id, 2, 0)));
+    assert_eq!(tx_count(&database, &rollup_id), TransactionOrder::from(1));
+    assert_eq!(stored(&database, &rollup_id, 2, 0), "2-0");
+}
+
+#[test]
+fn resubmissions_are_synced_in_contiguous_batches() {
+    let rollup_id = RollupId::from("1");
+    let sequenced = |block_height, tx_order| {
+        let sync = sync_transaction(&rollup_id, block_height, tx_order);
+        SequencedTransaction {
+            raw_tx: sync.raw_tx,
+            order_commitment: sync.order_commitment,
+        }
+    };
+
+    // A resubmitted batch mixes new transactions with ones of earlier block heights.
+    let transactions = vec![
+        sequenced(2, 0),
+        sequenced(1, 4),
+        sequenced(2, 1),
+        sequenced(1, 3),
+        sequenced(1, 4),
+        sequenced(1, 7),
+    ];
+    let batches: Vec<(usize, Vec<String>)> = sync::batches(&rollup_id, transactions)
+        .into_iter()
+        .map(|batch| {
+            let raw_txs = batch
+                .transactions
+                .iter()
+                .map(|transaction| transaction.raw_tx.to_string())
+                .collect();
+            (batch.block_height.value(), raw_txs)
+        })
+        .collect();
+    assert_eq!(
+        batches,
+        [
+            (1, vec!["1-3".to_string(), "1-4".to_string()]),
+            (1, vec!["1-7".to_string()]),
+            (2, vec!["2-0".to_string(), "2-1".to_string()]),
+        ],
+    );
+}
+
+#[test]
+fn durability_modes_require_acknowledgements() {
+    let modes =
+        ["leader-only", "majority", "all"].map(|mode| mode.parse::<DurabilityMode>().unwrap());
+    let required_acks = |set_size| modes.map(|mode| mode.required_acks(set_size));
+    assert_eq!(required_acks(1), [0, 0, 0]);
+    assert_eq!(required_acks(2), [0, 1, 1]);
+    assert_eq!(required_acks(3), [0, 1, 2]);
+    assert_eq!(required_acks(4), [0, 2, 3]);
+    assert!("quorum".parse::<DurabilityMode>().is_err());
+}
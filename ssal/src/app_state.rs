@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use ssal_api::ssal::ADMIN_KEY_HEADER;
+use ssal_core::{
+    axum::http::HeaderMap,
+    error::{Error, ErrorCode},
+};
+use ssal_database::Database;
+
+pub struct AppState {
+    database: Database,
+    admin_key: Option<Arc<String>>,
+}
+
+impl Clone for AppState {
+    fn clone(&self) -> Self {
+        Self {
+            database: self.database.clone(),
+            admin_key: self.admin_key.clone(),
+        }
+    }
+}
+
+impl AppState {
+    pub fn new(database: Database, admin_key: Option<String>) -> Self {
+        Self {
+            database,
+            admin_key: admin_key.map(Arc::new),
+        }
+    }
+
+    pub fn database(&self) -> &Database {
+        &self.database
+    }
+
+    /// Admin endpoints are disabled unless the server was started with an admin key.
+    pub fn is_admin(&self, admin_key: Option<&str>) -> bool {
+        match (&self.admin_key, admin_key) {
+            (Some(expected), Some(admin_key)) => expected.as_str() == admin_key,
+            _others => false,
+        }
+    }
+
+    /// Reject the request unless it carries the admin key in the [`ADMIN_KEY_HEADER`]
+    /// header.
+    pub fn check_admin(&self, headers: &HeaderMap) -> Result<(), Error> {
+        let admin_key = headers
+            .get(ADMIN_KEY_HEADER)
+            .and_then(|admin_key| admin_key.to_str().ok());
+        match self.is_admin(admin_key) {
+            true => Ok(()),
+            false => Err(Error::new(
+                ErrorCode::Unauthorized,
+                "Unauthorized admin request.",
+            )),
+        }
+    }
+}
@@ -1,48 +1,108 @@
 use super::prelude::*;
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(crate = "ssal_core::serde")]
-pub struct CloseBlock {
-    rollup_id: RollupId,
-}
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<CloseBlock>,
+) -> Result<impl IntoResponse, Error> {
+    tracing::info!("[CloseBlock]: {:?}", payload.rollup_id);
+
+    // Paused and retired rollups do not close blocks.
+    let rollup_set: RollupSet = state.database().get(&"rollup_set")?;
+    rollup_set.check_operational(&payload.rollup_id)?;
+
+    // Get the current block height. The block height only advances together with the
+    // election below, in the same transaction, so that a failed election leaves the
+    // sequencer set open instead of skipping it.
+    let mut block_height: Lock<BlockHeight> = state
+        .database()
+        .get_mut(&("block_height", &payload.rollup_id))?;
+    let current_block_height = block_height.clone();
 
-impl CloseBlock {
-    pub async fn handler(
-        State(state): State<Database>,
-        Json(payload): Json<Self>,
-    ) -> Result<impl IntoResponse, Error> {
-        tracing::info!("[CloseBlock]: {:?}", payload.rollup_id);
-
-        // Get the current block height.
-        let mut block_height: Lock<BlockHeight> =
-            state.get_mut(&("block_height", &payload.rollup_id))?;
-        let current_block_height = block_height.clone();
-        block_height.increment();
-        block_height.commit()?;
-
-        // Always use the current block height.
-        // Elect the leader.
-        let registered_sequencers_key =
-            ("sequencer_set", &payload.rollup_id, &current_block_height);
-        let mut sequencer_set: Lock<SequencerSet> = state.get_mut(&registered_sequencers_key)?;
-        let leader_id = sequencer_set.elect_leader()?;
-
-        // Advertise the sequencer_set.
-        state.put(
-            &(
-                "closed_sequencer_set",
-                &payload.rollup_id,
-                &current_block_height,
+    // Check again with the rollup set locked until the block height advances, so that the
+    // rollup cannot be paused or retired meanwhile.
+    let mut rollup_set: RollupSet = block_height.get_for_update(&"rollup_set")?;
+    rollup_set.check_operational(&payload.rollup_id)?;
+
+    // Always use the current block height.
+    // Elect the leader with the seed derived from the previously closed sequencer set
+    // and the latest block commitment.
+    let registered_sequencers_key = ("sequencer_set", &payload.rollup_id, &current_block_height);
+    let mut sequencer_set: SequencerSet =
+        block_height.get_for_update(&registered_sequencers_key)?;
+    let exclusion_list: ExclusionList = state
+        .database()
+        .get(&("exclusion_list", &payload.rollup_id))?;
+    sequencer_set.remove_excluded(&exclusion_list);
+    if sequencer_set.candidates().is_empty() {
+        return Err(Error::new(
+            ErrorCode::ElectionFailed,
+            format!(
+                "No candidate is left in the sequencer set of {:?}",
+                current_block_height,
             ),
-            &*sequencer_set,
-        )?;
-        sequencer_set.commit()?;
-
-        tracing::info!(
-            "[CloseBlock]: Successfully elected the leader for {:?}: {:?}",
-            payload.rollup_id,
-            current_block_height,
-        );
-        Ok((StatusCode::OK, Json(leader_id)))
+        ));
+    }
+    let rollup_config: RollupConfig = state
+        .database()
+        .get(&("rollup_config", &payload.rollup_id))?;
+    let election_seed_key = ("election_seed", &payload.rollup_id);
+    let election_seed: ElectionSeed = block_height.get_for_update(&election_seed_key)?;
+
+    // Mix in the commitment of the block two heights below. The block of the previous
+    // height has only just ended and is not built yet. The commitment is final once the
+    // leader or a failover quorum of the followers submitted it, so a leader withholding
+    // it does not change the seed. Without it, the seed is not mixed: a leader can only
+    // bias the election by one choice, by withholding while fewer than a quorum of
+    // followers submit in time, as in sets of fewer than three sequencers.
+    let block_commitment = match current_block_height.value() > 2 {
+        true => match block_height.get_for_update::<_, BlockCommitment>(&(
+            "block_commitment",
+            &payload.rollup_id,
+            &(current_block_height.clone() - 2),
+        )) {
+            Ok(block_commitment) => Some(block_commitment),
+            Err(error) if error.is_none_type() => None,
+            Err(error) => return Err(error),
+        },
+        false => None,
+    };
+    let leader_id = sequencer_set.elect_leader(
+        &payload.rollup_id,
+        election_seed,
+        block_commitment,
+        rollup_config.election_method(),
+    )?;
+
+    // Chain the seed for the next election.
+    let next_seed = sequencer_set.next_seed().wrap_as(
+        ErrorCode::ElectionFailed,
+        "Failed to derive the next election seed",
+    )?;
+    block_height.put(&election_seed_key, &next_seed)?;
+
+    // Advertise the sequencer_set.
+    block_height.put(
+        &(
+            "closed_sequencer_set",
+            &payload.rollup_id,
+            &current_block_height,
+        ),
+        &sequencer_set,
+    )?;
+    block_height.put(&registered_sequencers_key, &sequencer_set)?;
+
+    // The first closed block activates the rollup.
+    if rollup_set.state(&payload.rollup_id)? == RollupState::Registered {
+        rollup_set.transition(&payload.rollup_id, RollupState::Active)?;
+        block_height.put(&"rollup_set", &rollup_set)?;
     }
+    block_height.increment();
+    block_height.commit()?;
+
+    tracing::info!(
+        "[CloseBlock]: Successfully elected the leader for {:?}: {:?}",
+        payload.rollup_id,
+        current_block_height,
+    );
+    Ok((StatusCode::OK, Json(leader_id)))
 }
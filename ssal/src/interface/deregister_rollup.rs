@@ -0,0 +1,15 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<DeregisterRollup>,
+) -> Result<impl IntoResponse, Error> {
+    state.check_admin(&headers)?;
+    tracing::info!("[DeregisterRollup]: {:?}", payload.rollup_id);
+
+    let mut rollup_set: Lock<RollupSet> = state.database().get_mut(&"rollup_set")?;
+    rollup_set.deregister(&payload.rollup_id)?;
+    rollup_set.commit()?;
+    Ok((StatusCode::OK, ()))
+}
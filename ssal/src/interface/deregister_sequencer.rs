@@ -0,0 +1,45 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<DeregisterSequencer>,
+) -> Result<impl IntoResponse, Error> {
+    tracing::info!(
+        "[DeregisterSequencer]: {:?}: {:?}",
+        payload.rollup_id,
+        payload.sequencer_id,
+    );
+
+    let block_height: Lock<BlockHeight> = state
+        .database()
+        .get_mut(&("block_height", &payload.rollup_id))?;
+
+    // The block height is signed so that the request cannot be replayed against later
+    // sequencer sets.
+    if payload.block_height != *block_height {
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "The sequencer set of {:?} is not open for registration",
+                payload.block_height,
+            ),
+        ));
+    }
+
+    let mut sequencer_set: Lock<SequencerSet> =
+        state
+            .database()
+            .get_mut(&("sequencer_set", &payload.rollup_id, &*block_height))?;
+    sequencer_set.authenticate(
+        &payload.sequencer_id,
+        DeregisterSequencer::digest(
+            &payload.rollup_id,
+            &payload.sequencer_id,
+            &payload.block_height,
+        ),
+        &payload.signature,
+    )?;
+    sequencer_set.deregister(&payload.sequencer_id)?;
+    sequencer_set.commit()?;
+    Ok((StatusCode::OK, Json(block_height.clone())))
+}
@@ -0,0 +1,50 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<ExcludeSequencer>,
+) -> Result<impl IntoResponse, Error> {
+    state.check_admin(&headers)?;
+
+    tracing::info!(
+        "[ExcludeSequencer]: {:?}: {:?} ({})",
+        payload.rollup_id,
+        payload.sequencer_id,
+        payload.reason,
+    );
+
+    let block_height: Lock<BlockHeight> = state
+        .database()
+        .get_mut(&("block_height", &payload.rollup_id))?;
+    let until = payload
+        .block_count
+        .map(|block_count| block_height.clone() + block_count);
+    let exclusion = Exclusion::new(payload.reason, block_height.clone(), until);
+
+    let mut exclusion_list: Lock<ExclusionList> = state
+        .database()
+        .get_mut(&("exclusion_list", &payload.rollup_id))?;
+    exclusion_list.exclude(payload.sequencer_id.clone(), exclusion);
+
+    // Drop the sequencer from the set which is open for registration.
+    let sequencer_set_key = ("sequencer_set", &payload.rollup_id, &*block_height);
+    match state
+        .database()
+        .get_mut::<(&str, &RollupId, &BlockHeight), SequencerSet>(&sequencer_set_key)
+    {
+        Ok(mut sequencer_set) => {
+            if sequencer_set.contains(&payload.sequencer_id) {
+                sequencer_set.deregister(&payload.sequencer_id)?;
+                sequencer_set.commit()?;
+            }
+        }
+        Err(error) => {
+            if !error.is_none_type() {
+                return Err(error);
+            }
+        }
+    }
+    exclusion_list.commit()?;
+    Ok((StatusCode::OK, ()))
+}
@@ -0,0 +1,11 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetBlockHeight>,
+) -> Result<impl IntoResponse, Error> {
+    let block_height: BlockHeight = state
+        .database()
+        .get(&("block_height", &parameter.rollup_id))?;
+    Ok((StatusCode::OK, Json(block_height)))
+}
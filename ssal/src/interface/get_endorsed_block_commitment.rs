@@ -0,0 +1,29 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetEndorsedBlockCommitment>,
+) -> Result<impl IntoResponse, Error> {
+    // Endorsements keep coming in after the commitment is final, so look up the final one.
+    let block_commitment: BlockCommitment = state.database().get(&(
+        "block_commitment",
+        &parameter.rollup_id,
+        &parameter.block_height,
+    ))?;
+    let endorsements: Vec<EndorsedBlockCommitment> = state.database().get(&(
+        "block_commitment_endorsements",
+        &parameter.rollup_id,
+        &parameter.block_height,
+    ))?;
+    let endorsed_block_commitment = endorsements
+        .into_iter()
+        .find(|endorsed| *endorsed.block_commitment() == block_commitment)
+        .wrap_as(
+            ErrorCode::NotFound,
+            format!(
+                "No endorsements of the block commitment for {:?}",
+                parameter.block_height,
+            ),
+        )?;
+    Ok((StatusCode::OK, Json(endorsed_block_commitment)))
+}
@@ -0,0 +1,11 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetExclusionList>,
+) -> Result<impl IntoResponse, Error> {
+    let exclusion_list: ExclusionList = state
+        .database()
+        .get(&("exclusion_list", &parameter.rollup_id))?;
+    Ok((StatusCode::OK, Json(exclusion_list)))
+}
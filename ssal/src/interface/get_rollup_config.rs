@@ -0,0 +1,11 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetRollupConfig>,
+) -> Result<impl IntoResponse, Error> {
+    let rollup_config: RollupConfig = state
+        .database()
+        .get(&("rollup_config", &parameter.rollup_id))?;
+    Ok((StatusCode::OK, Json(rollup_config)))
+}
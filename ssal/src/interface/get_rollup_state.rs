@@ -0,0 +1,10 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Query(parameter): Query<GetRollupState>,
+) -> Result<impl IntoResponse, Error> {
+    let rollup_set: RollupSet = state.database().get(&"rollup_set")?;
+    let rollup_state = rollup_set.state(&parameter.rollup_id)?;
+    Ok((StatusCode::OK, Json(rollup_state)))
+}
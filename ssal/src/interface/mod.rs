@@ -1,25 +1,38 @@
-mod close_block;
-mod get_closed_sequencer_set;
-mod get_sequencer_set;
-mod register_rollup;
-mod register_sequencer;
+pub mod close_block;
+pub mod deregister_rollup;
+pub mod deregister_sequencer;
+pub mod exclude_sequencer;
+pub mod get_block_height;
+pub mod get_closed_sequencer_set;
+pub mod get_endorsed_block_commitment;
+pub mod get_exclusion_list;
+pub mod get_rollup_config;
+pub mod get_rollup_state;
+pub mod get_sequencer_set;
+pub mod pause_rollup;
+pub mod register_rollup;
+pub mod register_sequencer;
+pub mod report_leader_failure;
+pub mod resume_rollup;
+pub mod submit_block_commitment;
 pub mod prelude {
+    pub use ssal_api::ssal::*;
     pub use ssal_core::{
         axum::{
             extract::{Query, State},
-            http::StatusCode,
+            http::{HeaderMap, StatusCode},
             response::IntoResponse,
             Json,
         },
-        error::{Error, WrapError},
+        election::*,
+        error::{Error, ErrorCode, WrapError},
+        ethers_core::types::Address,
         rand::{self, seq::SliceRandom},
         serde::{Deserialize, Serialize},
         tracing,
         types::*,
     };
     pub use ssal_database::{Database, Lock};
+
+    pub use crate::app_state::AppState;
 }
-pub use self::{
-    close_block::*, get_closed_sequencer_set::*, get_sequencer_set::*, register_rollup::*,
-    register_sequencer::*,
-};
@@ -1,36 +1,102 @@
 use super::prelude::*;
 
-#[derive(Deserialize, Serialize)]
-#[serde(crate = "ssal_core::serde")]
-pub struct RegisterSequencer {
-    rollup_id: RollupId,
-    sequencer_id: SequencerId,
-}
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<RegisterSequencer>,
+) -> Result<impl IntoResponse, Error> {
+    let rollup_set: RollupSet = state.database().get(&"rollup_set")?;
+    rollup_set.check_operational(&payload.rollup_id)?;
+
+    // A stake-weighted election never picks a sequencer without stake.
+    let rollup_config: RollupConfig = state
+        .database()
+        .get(&("rollup_config", &payload.rollup_id))?;
+    if rollup_config.election_method() == ElectionMethod::StakeWeighted
+        && payload.stake.value() == 0
+    {
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            "Stake-weighted rollups require a positive stake.",
+        ));
+    }
+
+    // Bind the sequencer to the wallet address which signed the registration.
+    let address = payload
+        .signature
+        .recover(
+            RegisterSequencer::digest(
+                &payload.rollup_id,
+                &payload.sequencer_id,
+                &payload.stake,
+                &payload.block_height,
+            )
+            .to_vec(),
+        )
+        .wrap_as(
+            ErrorCode::InvalidSignature,
+            "Failed to recover the address of the sequencer",
+        )?;
+
+    let block_height_key = ("block_height", &payload.rollup_id);
+    let block_height: Lock<BlockHeight> = state.database().get_mut(&block_height_key)?;
+
+    // Check again with the rollup set locked until the registration is written, so that
+    // the rollup cannot be paused or retired meanwhile.
+    let rollup_set: RollupSet = block_height.get_for_update(&"rollup_set")?;
+    rollup_set.check_operational(&payload.rollup_id)?;
 
-impl RegisterSequencer {
-    pub async fn handler(
-        State(state): State<Database>,
-        Json(payload): Json<Self>,
-    ) -> Result<impl IntoResponse, Error> {
-        let block_height_key = ("block_height", &payload.rollup_id);
-        let block_height: Lock<BlockHeight> = state.get_mut(&block_height_key)?;
-
-        let sequencer_set_key = ("sequencer_set", &payload.rollup_id, &*block_height);
-        match state.get_mut::<(&str, &RollupId, &BlockHeight), SequencerSet>(&sequencer_set_key) {
-            Ok(mut sequencer_set) => {
-                sequencer_set.register(payload.sequencer_id)?;
-                sequencer_set.commit()?;
-                Ok((StatusCode::OK, Json(block_height.clone())))
-            }
-            Err(error) => match error.is_none_type() {
-                true => {
-                    let mut sequencer_set = SequencerSet::new(block_height.clone());
-                    sequencer_set.register(payload.sequencer_id)?;
-                    state.put(&sequencer_set_key, &sequencer_set)?;
-                    Ok((StatusCode::OK, Json(block_height.clone())))
-                }
-                false => Err(error),
-            },
+    // The block height is signed so that the registration cannot be replayed against
+    // later sequencer sets.
+    if payload.block_height != *block_height {
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "The sequencer set of {:?} is not open for registration",
+                payload.block_height,
+            ),
+        ));
+    }
+
+    // Reject the sequencer while it is excluded.
+    let exclusion_list: ExclusionList = state
+        .database()
+        .get(&("exclusion_list", &payload.rollup_id))?;
+    if let Some(exclusion) = exclusion_list.active(&payload.sequencer_id, &block_height) {
+        return Err(Error::new(
+            ErrorCode::SequencerExcluded,
+            format!("Sequencer is excluded: {}", exclusion.reason()),
+        ));
+    }
+
+    // The first registration binds the sequencer ID to its address for the rollup, so a
+    // registration which was altered after signing, or signed by another key, is rejected.
+    let address_key = (
+        "sequencer_address",
+        &payload.rollup_id,
+        &payload.sequencer_id,
+    );
+    match block_height.get_for_update::<_, Address>(&address_key) {
+        Ok(registered_address) if registered_address != address => {
+            return Err(Error::new(
+                ErrorCode::InvalidSignature,
+                format!("The request was not signed by {:?}", payload.sequencer_id),
+            ))
         }
+        Ok(_registered_address) => {}
+        Err(error) if error.is_none_type() => block_height.put(&address_key, &address)?,
+        Err(error) => return Err(error),
     }
+
+    let sequencer_set_key = ("sequencer_set", &payload.rollup_id, &*block_height);
+    let mut sequencer_set = match block_height.get_for_update(&sequencer_set_key) {
+        Ok(sequencer_set) => sequencer_set,
+        Err(error) if error.is_none_type() => SequencerSet::new(block_height.clone()),
+        Err(error) => return Err(error),
+    };
+    sequencer_set.register(payload.sequencer_id, address, payload.stake)?;
+    block_height.put(&sequencer_set_key, &sequencer_set)?;
+
+    let response = block_height.clone();
+    block_height.commit()?;
+    Ok((StatusCode::OK, Json(response)))
 }
@@ -0,0 +1,87 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ReportLeaderFailure>,
+) -> Result<impl IntoResponse, Error> {
+    tracing::info!(
+        "[ReportLeaderFailure]: {:?}: {:?} reported {:?}",
+        payload.rollup_id,
+        payload.block_height,
+        payload.leader_id,
+    );
+
+    let closed_sequencer_set_key = (
+        "closed_sequencer_set",
+        &payload.rollup_id,
+        &payload.block_height,
+    );
+    let mut sequencer_set: Lock<SequencerSet> =
+        state.database().get_mut(&closed_sequencer_set_key)?;
+    let leader_id = sequencer_set.leader().wrap_as(
+        ErrorCode::ElectionFailed,
+        "The leader has not been elected.",
+    )?;
+
+    // The leader has already been replaced.
+    if leader_id != payload.leader_id {
+        return Ok((StatusCode::OK, Json(leader_id)));
+    }
+
+    if payload.reporter_id == leader_id
+        || !sequencer_set.contains(&payload.reporter_id)
+        || sequencer_set
+            .failed_leaders()
+            .contains(&payload.reporter_id)
+    {
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            "Only the followers in the sequencer set can report the leader.",
+        ));
+    }
+    sequencer_set.authenticate(
+        &payload.reporter_id,
+        ReportLeaderFailure::digest(
+            &payload.rollup_id,
+            &payload.block_height,
+            &payload.reporter_id,
+            &payload.leader_id,
+        ),
+        &payload.signature,
+    )?;
+
+    let report_key = (
+        "leader_failure_report",
+        &payload.rollup_id,
+        &payload.block_height,
+    );
+    let mut report = match state
+        .database()
+        .get::<(&str, &RollupId, &BlockHeight), LeaderFailureReport>(&report_key)
+    {
+        Ok(report) if *report.leader_id() == leader_id => report,
+        Ok(_previous_leader) => LeaderFailureReport::new(leader_id.clone()),
+        Err(error) => match error.is_none_type() {
+            true => LeaderFailureReport::new(leader_id.clone()),
+            false => return Err(error),
+        },
+    };
+
+    let report_count = report.report(payload.reporter_id);
+    if report_count < sequencer_set.failover_quorum() {
+        state.database().put(&report_key, &report)?;
+        return Ok((StatusCode::OK, Json(leader_id)));
+    }
+
+    let replacement_id = sequencer_set.fail_over(&payload.rollup_id)?;
+    sequencer_set.commit()?;
+    state.database().delete(&report_key)?;
+
+    tracing::info!(
+        "[ReportLeaderFailure]: Replaced the leader for {:?}: {:?} with {:?}",
+        payload.rollup_id,
+        payload.block_height,
+        replacement_id,
+    );
+    Ok((StatusCode::OK, Json(replacement_id)))
+}
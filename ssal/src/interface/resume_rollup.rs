@@ -0,0 +1,15 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<ResumeRollup>,
+) -> Result<impl IntoResponse, Error> {
+    state.check_admin(&headers)?;
+    tracing::info!("[ResumeRollup]: {:?}", payload.rollup_id);
+
+    let mut rollup_set: Lock<RollupSet> = state.database().get_mut(&"rollup_set")?;
+    rollup_set.transition(&payload.rollup_id, RollupState::Active)?;
+    rollup_set.commit()?;
+    Ok((StatusCode::OK, ()))
+}
@@ -0,0 +1,110 @@
+use super::prelude::*;
+
+pub async fn handler(
+    State(state): State<AppState>,
+    Json(payload): Json<SubmitBlockCommitment>,
+) -> Result<impl IntoResponse, Error> {
+    tracing::info!(
+        "[SubmitBlockCommitment]: {:?}: {:?} from {:?}",
+        payload.rollup_id,
+        payload.block_height,
+        payload.sequencer_id,
+    );
+
+    // Every member of the closed sequencer set builds the block, but a leader which
+    // failed over does not endorse its commitment.
+    let closed_sequencer_set_key = (
+        "closed_sequencer_set",
+        &payload.rollup_id,
+        &payload.block_height,
+    );
+    let sequencer_set: Lock<SequencerSet> = state.database().get_mut(&closed_sequencer_set_key)?;
+    let digest = SubmitBlockCommitment::digest(
+        &payload.rollup_id,
+        &payload.block_height,
+        &payload.block_commitment,
+    );
+    sequencer_set.authenticate(&payload.sequencer_id, digest, &payload.signature)?;
+    if sequencer_set
+        .failed_leaders()
+        .contains(&payload.sequencer_id)
+    {
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            "A leader which failed over cannot submit the block commitment.",
+        ));
+    }
+
+    // The final commitment does not change so that the election it is mixed into can be
+    // recomputed.
+    let block_commitment_key = (
+        "block_commitment",
+        &payload.rollup_id,
+        &payload.block_height,
+    );
+    let different_commitment = || {
+        Error::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "A different block commitment was submitted for {:?}",
+                payload.block_height,
+            ),
+        )
+    };
+    match sequencer_set.get_for_update::<_, BlockCommitment>(&block_commitment_key) {
+        Ok(block_commitment) if block_commitment == payload.block_commitment => {
+            return Ok((StatusCode::OK, Json(())))
+        }
+        Ok(_block_commitment) => return Err(different_commitment()),
+        Err(error) if error.is_none_type() => {}
+        Err(error) => return Err(error),
+    }
+
+    // Each sequencer endorses a single commitment.
+    let endorsements_key = (
+        "block_commitment_endorsements",
+        &payload.rollup_id,
+        &payload.block_height,
+    );
+    let mut endorsements: Vec<EndorsedBlockCommitment> =
+        match sequencer_set.get_for_update(&endorsements_key) {
+            Ok(endorsements) => endorsements,
+            Err(error) if error.is_none_type() => Vec::new(),
+            Err(error) => return Err(error),
+        };
+    if endorsements.iter().any(|endorsed| {
+        endorsed.is_endorsed_by(&payload.sequencer_id)
+            && *endorsed.block_commitment() != payload.block_commitment
+    }) {
+        return Err(different_commitment());
+    }
+    let position = match endorsements
+        .iter()
+        .position(|endorsed| *endorsed.block_commitment() == payload.block_commitment)
+    {
+        Some(position) => position,
+        None => {
+            endorsements.push(EndorsedBlockCommitment::new(
+                payload.block_commitment.clone(),
+            ));
+            endorsements.len() - 1
+        }
+    };
+    endorsements[position].endorse(payload.sequencer_id, payload.signature);
+    let is_final = endorsements[position].is_final(&sequencer_set, digest);
+
+    sequencer_set.put(&endorsements_key, &endorsements)?;
+    if is_final {
+        sequencer_set.put(&block_commitment_key, &payload.block_commitment)?;
+    }
+    sequencer_set.commit()?;
+
+    if is_final {
+        tracing::info!(
+            "[SubmitBlockCommitment]: Successfully stored the block commitment for {:?}: {:?}",
+            payload.rollup_id,
+            payload.block_height,
+        );
+    }
+    Ok((StatusCode::OK, Json(())))
+}
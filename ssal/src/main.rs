@@ -1,6 +1,7 @@
 use std::env;
 
-use ssal::interface::*;
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::{ssal::*, Endpoint};
 use ssal_core::{
     axum::{
         self,
@@ -35,22 +36,49 @@ async fn main() -> Result<(), Error> {
         .join("databases/ssal");
     let database = Database::new(database_path)?;
 
+    // Admin endpoints are disabled without an admin key.
+    let admin_key = env_variables.get(1).cloned();
+
     // Initialize the rollup set.
     let rollup_set = RollupSet::default();
     database.put(&"rollup_set", &rollup_set)?;
 
     // Set handlers.
     let app = Router::new()
-        .route("/close-block", post(CloseBlock::handler))
-        .route("/get-sequencer-set", get(GetSequencerSet::handler))
+        .route(CloseBlock::PATH, post(close_block::handler))
+        .route(GetSequencerSet::PATH, get(get_sequencer_set::handler))
+        .route(
+            GetClosedSequencerSet::PATH,
+            get(get_closed_sequencer_set::handler),
+        )
+        .route(RegisterRollup::PATH, post(register_rollup::handler))
+        .route(PauseRollup::PATH, post(pause_rollup::handler))
+        .route(ResumeRollup::PATH, post(resume_rollup::handler))
+        .route(DeregisterRollup::PATH, post(deregister_rollup::handler))
+        .route(GetRollupState::PATH, get(get_rollup_state::handler))
+        .route(GetRollupConfig::PATH, get(get_rollup_config::handler))
+        .route(GetBlockHeight::PATH, get(get_block_height::handler))
+        .route(RegisterSequencer::PATH, post(register_sequencer::handler))
+        .route(
+            DeregisterSequencer::PATH,
+            post(deregister_sequencer::handler),
+        )
+        .route(ExcludeSequencer::PATH, post(exclude_sequencer::handler))
+        .route(GetExclusionList::PATH, get(get_exclusion_list::handler))
+        .route(
+            ReportLeaderFailure::PATH,
+            post(report_leader_failure::handler),
+        )
+        .route(
+            SubmitBlockCommitment::PATH,
+            post(submit_block_commitment::handler),
+        )
         .route(
-            "/get-closed-sequencer-set",
-            get(GetClosedSequencerSet::handler),
+            GetEndorsedBlockCommitment::PATH,
+            get(get_endorsed_block_commitment::handler),
         )
-        .route("/register-rollup", post(RegisterRollup::handler))
-        .route("/register-sequencer", post(RegisterSequencer::handler))
         .layer(CorsLayer::permissive())
-        .with_state(database);
+        .with_state(AppState::new(database, admin_key));
 
     // Start the server.
     tracing::info!("Starting the server at {:?}", address);
@@ -0,0 +1,58 @@
+mod common;
+
+use common::Sequencer;
+use ssal::interface::*;
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{extract::State, Json},
+    election::ElectionMethod,
+    error::ErrorCode,
+    tokio,
+    types::*,
+};
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn stake_weighted_rollups_reject_sequencers_without_stake() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::StakeWeighted).await;
+
+    let error = Sequencer::new(8000)
+        .register(&state, &rollup_id, 0)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidRequest);
+    assert!(Sequencer::new(8001)
+        .register(&state, &rollup_id, 1)
+        .await
+        .is_ok());
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn failed_elections_keep_the_block_height() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencer = Sequencer::new(8000);
+    sequencer.register(&state, &rollup_id, 0).await.unwrap();
+
+    // Excluding the only sequencer leaves no candidate.
+    let payload = ExcludeSequencer {
+        rollup_id: rollup_id.clone(),
+        sequencer_id: sequencer.sequencer_id.clone(),
+        reason: "test".into(),
+        block_count: None,
+    };
+    assert!(exclude_sequencer::handler(
+        State(state.clone()),
+        common::admin_headers(common::ADMIN_KEY),
+        Json(payload),
+    )
+    .await
+    .is_ok());
+
+    let error = common::close_block(&state, &rollup_id).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::ElectionFailed);
+    let block_height: BlockHeight = state.database().get(&("block_height", &rollup_id)).unwrap();
+    assert_eq!(block_height.value(), 1);
+}
@@ -0,0 +1,114 @@
+// Each test crate uses a different part of the helpers.
+#![allow(dead_code)]
+
+use ethers::signers::{LocalWallet, Signer};
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{extract::State, http::HeaderMap, Json},
+    election::ElectionMethod,
+    error::Error,
+    ethers_core::types::Signature,
+    types::*,
+};
+use ssal_database::Database;
+use tempfile::TempDir;
+
+pub const ADMIN_KEY: &str = "admin-key";
+
+/// An SSAL server state on a database which is removed with the returned directory.
+pub fn app_state() -> (TempDir, AppState) {
+    let database_dir = TempDir::new().unwrap();
+    let database = Database::new(database_dir.path()).unwrap();
+    database.put(&"rollup_set", &RollupSet::default()).unwrap();
+    (
+        database_dir,
+        AppState::new(database, Some(ADMIN_KEY.to_string())),
+    )
+}
+
+pub fn admin_headers(admin_key: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(ADMIN_KEY_HEADER, admin_key.parse().unwrap());
+    headers
+}
+
+/// A sequencer with its wallet key.
+pub struct Sequencer {
+    pub sequencer_id: SequencerId,
+    pub wallet: LocalWallet,
+}
+
+impl Sequencer {
+    pub fn new(port: u16) -> Self {
+        Self {
+            sequencer_id: format!("127.0.0.1:{}", port).into(),
+            wallet: LocalWallet::new(&mut ssal_core::rand::thread_rng()),
+        }
+    }
+
+    pub async fn sign(&self, digest: [u8; 32]) -> Signature {
+        self.wallet.sign_message(digest).await.unwrap()
+    }
+
+    /// A registration for the sequencer set which is open at `block_height`.
+    pub async fn registration(
+        &self,
+        rollup_id: &RollupId,
+        stake: Stake,
+        block_height: BlockHeight,
+    ) -> RegisterSequencer {
+        let digest =
+            RegisterSequencer::digest(rollup_id, &self.sequencer_id, &stake, &block_height);
+        RegisterSequencer {
+            rollup_id: rollup_id.clone(),
+            sequencer_id: self.sequencer_id.clone(),
+            stake,
+            block_height,
+            signature: self.sign(digest).await,
+        }
+    }
+
+    pub async fn register(
+        &self,
+        state: &AppState,
+        rollup_id: &RollupId,
+        stake: u64,
+    ) -> Result<(), Error> {
+        let block_height: BlockHeight = state.database().get(&("block_height", rollup_id)).unwrap();
+        let payload = self
+            .registration(rollup_id, stake.into(), block_height)
+            .await;
+        register_sequencer::handler(State(state.clone()), Json(payload))
+            .await
+            .map(|_| ())
+    }
+}
+
+pub async fn register_rollup(
+    state: &AppState,
+    rollup_id: &RollupId,
+    election_method: ElectionMethod,
+) {
+    let payload = RegisterRollup {
+        rollup_id: rollup_id.clone(),
+        election_method,
+        commitment_method: CommitmentMethod::default(),
+        curve: Curve::default(),
+        durability_mode: DurabilityMode::default(),
+    };
+    assert!(
+        register_rollup::handler(State(state.clone()), Json(payload))
+            .await
+            .is_ok()
+    );
+}
+
+pub async fn close_block(state: &AppState, rollup_id: &RollupId) -> Result<(), Error> {
+    let payload = CloseBlock {
+        rollup_id: rollup_id.clone(),
+    };
+    close_block::handler(State(state.clone()), Json(payload))
+        .await
+        .map(|_| ())
+}
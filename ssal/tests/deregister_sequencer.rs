@@ -0,0 +1,67 @@
+mod common;
+
+use common::Sequencer;
+use ssal::interface::*;
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{extract::State, Json},
+    election::ElectionMethod,
+    error::{Error, ErrorCode},
+    tokio,
+    types::*,
+};
+
+async fn deregister(
+    state: &ssal::app_state::AppState,
+    rollup_id: &RollupId,
+    sequencer: &Sequencer,
+    signer: &Sequencer,
+    block_height: usize,
+) -> Result<(), Error> {
+    let block_height = BlockHeight::from(block_height);
+    let payload = DeregisterSequencer {
+        rollup_id: rollup_id.clone(),
+        sequencer_id: sequencer.sequencer_id.clone(),
+        block_height: block_height.clone(),
+        signature: signer
+            .sign(DeregisterSequencer::digest(
+                rollup_id,
+                &sequencer.sequencer_id,
+                &block_height,
+            ))
+            .await,
+    };
+    deregister_sequencer::handler(State(state.clone()), Json(payload))
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn only_the_sequencer_deregisters_itself() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencer = Sequencer::new(8000);
+    let other = Sequencer::new(8001);
+    sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    other.register(&state, &rollup_id, 0).await.unwrap();
+
+    let error = deregister(&state, &rollup_id, &sequencer, &other, 1)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidSignature);
+    let error = deregister(&state, &rollup_id, &sequencer, &sequencer, 2)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidRequest);
+
+    deregister(&state, &rollup_id, &sequencer, &sequencer, 1)
+        .await
+        .unwrap();
+    let sequencer_set: SequencerSet = state
+        .database()
+        .get(&("sequencer_set", &rollup_id, &BlockHeight::from(1)))
+        .unwrap();
+    assert!(!sequencer_set.contains(&sequencer.sequencer_id));
+    assert!(sequencer_set.contains(&other.sequencer_id));
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::Sequencer;
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{
+        body::to_bytes,
+        extract::{Query, State},
+        response::IntoResponse,
+        Json,
+    },
+    election::ElectionMethod,
+    error::ErrorCode,
+    serde_json, tokio,
+    types::*,
+};
+
+async fn exclude(
+    state: &AppState,
+    rollup_id: &RollupId,
+    sequencer: &Sequencer,
+    reason: &str,
+    block_count: Option<usize>,
+) {
+    let payload = ExcludeSequencer {
+        rollup_id: rollup_id.clone(),
+        sequencer_id: sequencer.sequencer_id.clone(),
+        reason: reason.into(),
+        block_count,
+    };
+    assert!(exclude_sequencer::handler(
+        State(state.clone()),
+        common::admin_headers(common::ADMIN_KEY),
+        Json(payload),
+    )
+    .await
+    .is_ok());
+}
+
+async fn exclusion_list(state: &AppState, rollup_id: &RollupId) -> ExclusionList {
+    let parameter = GetExclusionList {
+        rollup_id: rollup_id.clone(),
+    };
+    let response = get_exclusion_list::handler(State(state.clone()), Query(parameter))
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn permanent_exclusions_are_never_downgraded() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencer = Sequencer::new(8000);
+    exclude(&state, &rollup_id, &sequencer, "equivocation", None).await;
+    exclude(&state, &rollup_id, &sequencer, "downtime", Some(1)).await;
+
+    // The short exclusion has expired after the next block, the permanent one has not.
+    let other = Sequencer::new(8001);
+    other.register(&state, &rollup_id, 0).await.unwrap();
+    common::close_block(&state, &rollup_id).await.unwrap();
+    let error = sequencer.register(&state, &rollup_id, 0).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::SequencerExcluded);
+
+    let exclusion_list = exclusion_list(&state, &rollup_id).await;
+    let exclusion = exclusion_list
+        .active(&sequencer.sequencer_id, &BlockHeight::from(1000))
+        .unwrap();
+    assert!(exclusion.is_permanent());
+    assert_eq!(exclusion.reason(), "equivocation");
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn every_exclusion_is_kept_with_its_reason() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencer = Sequencer::new(8000);
+    exclude(&state, &rollup_id, &sequencer, "downtime", Some(1)).await;
+
+    let other = Sequencer::new(8001);
+    other.register(&state, &rollup_id, 0).await.unwrap();
+    common::close_block(&state, &rollup_id).await.unwrap();
+    sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    exclude(&state, &rollup_id, &sequencer, "invalid blocks", Some(10)).await;
+
+    let exclusion_list = exclusion_list(&state, &rollup_id).await;
+    let history = exclusion_list.history(&sequencer.sequencer_id);
+    assert_eq!(
+        history
+            .iter()
+            .map(|exclusion| (exclusion.reason(), exclusion.from().value()))
+            .collect::<Vec<_>>(),
+        [("downtime", 1), ("invalid blocks", 2)],
+    );
+    assert_eq!(
+        exclusion_list
+            .active(&sequencer.sequencer_id, &BlockHeight::from(2))
+            .unwrap()
+            .reason(),
+        "invalid blocks",
+    );
+    assert!(exclusion_list.history(&other.sequencer_id).is_empty());
+}
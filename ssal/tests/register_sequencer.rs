@@ -0,0 +1,54 @@
+mod common;
+
+use common::Sequencer;
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::ssal::RegisterSequencer;
+use ssal_core::{
+    axum::{extract::State, Json},
+    election::ElectionMethod,
+    error::{Error, ErrorCode},
+    tokio,
+    types::*,
+};
+
+async fn register(state: &AppState, payload: &RegisterSequencer) -> Result<(), Error> {
+    register_sequencer::handler(State(state.clone()), Json(payload.clone()))
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn registrations_cannot_be_replayed_or_altered() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::StakeWeighted).await;
+    let sequencer = Sequencer::new(8000);
+    let captured = sequencer
+        .registration(&rollup_id, 10.into(), 1.into())
+        .await;
+    register(&state, &captured).await.unwrap();
+    common::close_block(&state, &rollup_id).await.unwrap();
+
+    // The registration was signed for the first block height only.
+    let error = register(&state, &captured).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidRequest);
+
+    // Any change to the signed registration recovers another address.
+    let mut replayed = captured.clone();
+    replayed.block_height = 2.into();
+    replayed.stake = 1000.into();
+    let error = register(&state, &replayed).await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidSignature);
+    let sequencer_set: Result<SequencerSet, _> =
+        state
+            .database()
+            .get(&("sequencer_set", &rollup_id, &BlockHeight::from(2)));
+    assert!(sequencer_set.is_err());
+
+    sequencer.register(&state, &rollup_id, 1000).await.unwrap();
+    let sequencer_set: SequencerSet = state
+        .database()
+        .get(&("sequencer_set", &rollup_id, &BlockHeight::from(2)))
+        .unwrap();
+    assert!(sequencer_set.contains(&sequencer.sequencer_id));
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use common::Sequencer;
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{body::to_bytes, extract::State, response::IntoResponse, Json},
+    election::ElectionMethod,
+    error::{Error, ErrorCode},
+    serde_json, tokio,
+    types::*,
+};
+
+async fn report(
+    state: &AppState,
+    rollup_id: &RollupId,
+    reporter: &Sequencer,
+    signer: &Sequencer,
+    leader_id: &SequencerId,
+) -> Result<SequencerId, Error> {
+    let block_height = BlockHeight::from(1);
+    let payload = ReportLeaderFailure {
+        rollup_id: rollup_id.clone(),
+        block_height: block_height.clone(),
+        reporter_id: reporter.sequencer_id.clone(),
+        leader_id: leader_id.clone(),
+        signature: signer
+            .sign(ReportLeaderFailure::digest(
+                rollup_id,
+                &block_height,
+                &reporter.sequencer_id,
+                leader_id,
+            ))
+            .await,
+    };
+    let response = report_leader_failure::handler(State(state.clone()), Json(payload)).await?;
+    let body = to_bytes(response.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    Ok(serde_json::from_slice(&body).unwrap())
+}
+
+fn closed_sequencer_set(state: &AppState, rollup_id: &RollupId) -> SequencerSet {
+    state
+        .database()
+        .get(&("closed_sequencer_set", rollup_id, &BlockHeight::from(1)))
+        .unwrap()
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_quorum_of_signed_reports_replaces_the_leader() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencers: Vec<Sequencer> = (8000..8004).map(Sequencer::new).collect();
+    for sequencer in sequencers.iter() {
+        sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    }
+    common::close_block(&state, &rollup_id).await.unwrap();
+
+    let sequencer_set = closed_sequencer_set(&state, &rollup_id);
+    let leader_id = sequencer_set.leader().unwrap();
+    let followers: Vec<&Sequencer> = sequencers
+        .iter()
+        .filter(|sequencer| sequencer.sequencer_id != leader_id)
+        .collect();
+    assert_eq!(sequencer_set.failover_quorum(), 2);
+
+    // Reports signed by another key do not count towards the quorum.
+    let error = report(&state, &rollup_id, followers[0], followers[1], &leader_id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidSignature);
+
+    let replacement_id = report(&state, &rollup_id, followers[0], followers[0], &leader_id)
+        .await
+        .unwrap();
+    assert_eq!(replacement_id, leader_id);
+    let replacement_id = report(&state, &rollup_id, followers[2], followers[2], &leader_id)
+        .await
+        .unwrap();
+    assert_ne!(replacement_id, leader_id);
+
+    // The failover is replayed from the closed set.
+    let sequencer_set = closed_sequencer_set(&state, &rollup_id);
+    assert_eq!(sequencer_set.leader(), Some(replacement_id));
+    assert_eq!(sequencer_set.failed_leaders(), &[leader_id]);
+    assert!(sequencer_set.verify_leader(&rollup_id));
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn forged_leaders_do_not_verify() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    for sequencer in (8000..8004).map(Sequencer::new) {
+        sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    }
+    common::close_block(&state, &rollup_id).await.unwrap();
+
+    let sequencer_set = closed_sequencer_set(&state, &rollup_id);
+    assert!(sequencer_set.verify_leader(&rollup_id));
+
+    let leader_id = sequencer_set.leader().unwrap();
+    for sequencer_id in sequencer_set
+        .iter()
+        .filter(|sequencer_id| **sequencer_id != leader_id)
+    {
+        let mut forged = serde_json::to_value(&sequencer_set).unwrap();
+        forged["leader"] = serde_json::to_value(sequencer_id).unwrap();
+        let forged: SequencerSet = serde_json::from_value(forged).unwrap();
+        assert!(!forged.verify_leader(&rollup_id));
+    }
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_single_follower_cannot_replace_the_leader() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencers: Vec<Sequencer> = (8000..8002).map(Sequencer::new).collect();
+    for sequencer in sequencers.iter() {
+        sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    }
+    common::close_block(&state, &rollup_id).await.unwrap();
+
+    let sequencer_set = closed_sequencer_set(&state, &rollup_id);
+    let leader_id = sequencer_set.leader().unwrap();
+    assert_eq!(sequencer_set.failover_quorum(), 2);
+    let follower = sequencers
+        .iter()
+        .find(|sequencer| sequencer.sequencer_id != leader_id)
+        .unwrap();
+    for _ in 0..2 {
+        let current_leader_id = report(&state, &rollup_id, follower, follower, &leader_id)
+            .await
+            .unwrap();
+        assert_eq!(current_leader_id, leader_id);
+    }
+    assert!(closed_sequencer_set(&state, &rollup_id)
+        .failed_leaders()
+        .is_empty());
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{
+        extract::{Query, State},
+        http::HeaderMap,
+        Json,
+    },
+    election::ElectionMethod,
+    error::{Error, ErrorCode},
+    tokio,
+    types::*,
+};
+
+async fn pause(state: &AppState, rollup_id: &RollupId, headers: HeaderMap) -> Result<(), Error> {
+    let payload = PauseRollup {
+        rollup_id: rollup_id.clone(),
+    };
+    pause_rollup::handler(State(state.clone()), headers, Json(payload))
+        .await
+        .map(|_| ())
+}
+
+async fn resume(state: &AppState, rollup_id: &RollupId, headers: HeaderMap) -> Result<(), Error> {
+    let payload = ResumeRollup {
+        rollup_id: rollup_id.clone(),
+    };
+    resume_rollup::handler(State(state.clone()), headers, Json(payload))
+        .await
+        .map(|_| ())
+}
+
+async fn deregister(
+    state: &AppState,
+    rollup_id: &RollupId,
+    headers: HeaderMap,
+) -> Result<(), Error> {
+    let payload = DeregisterRollup {
+        rollup_id: rollup_id.clone(),
+    };
+    deregister_rollup::handler(State(state.clone()), headers, Json(payload))
+        .await
+        .map(|_| ())
+}
+
+fn rollup_state(state: &AppState, rollup_id: &RollupId) -> RollupState {
+    let rollup_set: RollupSet = state.database().get(&"rollup_set").unwrap();
+    rollup_set.state(rollup_id).unwrap()
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn lifecycle_endpoints_require_the_admin_key() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencer = common::Sequencer::new(8000);
+    sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    common::close_block(&state, &rollup_id).await.unwrap();
+    assert_eq!(rollup_state(&state, &rollup_id), RollupState::Active);
+
+    for headers in [HeaderMap::new(), common::admin_headers("wrong-key")] {
+        let error = pause(&state, &rollup_id, headers.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Unauthorized);
+        let error = deregister(&state, &rollup_id, headers).await.unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Unauthorized);
+        assert_eq!(rollup_state(&state, &rollup_id), RollupState::Active);
+    }
+
+    let admin_headers = common::admin_headers(common::ADMIN_KEY);
+    pause(&state, &rollup_id, admin_headers.clone())
+        .await
+        .unwrap();
+    assert_eq!(rollup_state(&state, &rollup_id), RollupState::Paused);
+
+    for headers in [HeaderMap::new(), common::admin_headers("wrong-key")] {
+        let error = resume(&state, &rollup_id, headers).await.unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Unauthorized);
+        assert_eq!(rollup_state(&state, &rollup_id), RollupState::Paused);
+    }
+
+    resume(&state, &rollup_id, admin_headers.clone())
+        .await
+        .unwrap();
+    deregister(&state, &rollup_id, admin_headers).await.unwrap();
+    assert_eq!(rollup_state(&state, &rollup_id), RollupState::Retired);
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn an_open_sequencer_set_is_not_found_until_the_block_closes() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencer = common::Sequencer::new(8000);
+    sequencer.register(&state, &rollup_id, 0).await.unwrap();
+
+    let parameter = GetClosedSequencerSet {
+        rollup_id: rollup_id.clone(),
+        block_height: BlockHeight::from(1),
+    };
+    let error = get_closed_sequencer_set::handler(State(state.clone()), Query(parameter.clone()))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(error.code(), ErrorCode::NotFound);
+
+    common::close_block(&state, &rollup_id).await.unwrap();
+    assert!(
+        get_closed_sequencer_set::handler(State(state.clone()), Query(parameter))
+            .await
+            .is_ok()
+    );
+}
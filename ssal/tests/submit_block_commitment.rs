@@ -0,0 +1,218 @@
+mod common;
+
+use common::Sequencer;
+use ssal::{app_state::AppState, interface::*};
+use ssal_api::ssal::*;
+use ssal_core::{
+    axum::{
+        body::to_bytes,
+        extract::{Query, State},
+        response::IntoResponse,
+        Json,
+    },
+    election::ElectionMethod,
+    error::{Error, ErrorCode},
+    serde_json, tokio,
+    types::*,
+};
+
+async fn submit(
+    state: &AppState,
+    rollup_id: &RollupId,
+    signer: &Sequencer,
+    block_commitment: &BlockCommitment,
+) -> Result<(), Error> {
+    let block_height = BlockHeight::from(1);
+    let payload = SubmitBlockCommitment {
+        rollup_id: rollup_id.clone(),
+        block_height: block_height.clone(),
+        sequencer_id: signer.sequencer_id.clone(),
+        block_commitment: block_commitment.clone(),
+        signature: signer
+            .sign(SubmitBlockCommitment::digest(
+                rollup_id,
+                &block_height,
+                block_commitment,
+            ))
+            .await,
+    };
+    submit_block_commitment::handler(State(state.clone()), Json(payload))
+        .await
+        .map(|_| ())
+}
+
+async fn endorsed_block_commitment(
+    state: &AppState,
+    rollup_id: &RollupId,
+) -> Result<EndorsedBlockCommitment, Error> {
+    let parameter = GetEndorsedBlockCommitment {
+        rollup_id: rollup_id.clone(),
+        block_height: BlockHeight::from(1),
+    };
+    let response =
+        get_endorsed_block_commitment::handler(State(state.clone()), Query(parameter)).await?;
+    let body = to_bytes(response.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    Ok(serde_json::from_slice(&body).unwrap())
+}
+
+fn closed_sequencer_set(
+    state: &AppState,
+    rollup_id: &RollupId,
+    block_height: usize,
+) -> SequencerSet {
+    state
+        .database()
+        .get(&(
+            "closed_sequencer_set",
+            rollup_id,
+            &BlockHeight::from(block_height),
+        ))
+        .unwrap()
+}
+
+fn block_commitment(commitment: &[u8]) -> BlockCommitment {
+    BlockCommitment::new(
+        Curve::default(),
+        CommitmentMethod::default(),
+        commitment.to_vec().into(),
+    )
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn the_leader_commitment_is_mixed_into_the_election_seed() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencers: Vec<Sequencer> = (8000..8003).map(Sequencer::new).collect();
+    for sequencer in sequencers.iter() {
+        sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    }
+    common::close_block(&state, &rollup_id).await.unwrap();
+
+    let leader_id = closed_sequencer_set(&state, &rollup_id, 1)
+        .leader()
+        .unwrap();
+    let (leaders, followers): (Vec<&Sequencer>, Vec<&Sequencer>) = sequencers
+        .iter()
+        .partition(|sequencer| sequencer.sequencer_id == leader_id);
+    let leader = leaders[0];
+
+    // The leader alone makes the commitment final, and only one per block height.
+    let submitted = block_commitment(&[1, 2, 3]);
+    submit(
+        &state,
+        &rollup_id,
+        followers[0],
+        &block_commitment(&[7, 8, 9]),
+    )
+    .await
+    .unwrap();
+    submit(&state, &rollup_id, leader, &submitted)
+        .await
+        .unwrap();
+    submit(&state, &rollup_id, leader, &submitted)
+        .await
+        .unwrap();
+    let error = submit(&state, &rollup_id, leader, &block_commitment(&[4, 5, 6]))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidRequest);
+
+    for _ in 2..=3 {
+        for sequencer in sequencers.iter() {
+            sequencer.register(&state, &rollup_id, 0).await.unwrap();
+        }
+        common::close_block(&state, &rollup_id).await.unwrap();
+    }
+
+    let sequencer_sets: Vec<SequencerSet> = (1..=3)
+        .map(|block_height| closed_sequencer_set(&state, &rollup_id, block_height))
+        .collect();
+    assert_eq!(sequencer_sets[1].block_commitment(), None);
+    assert_eq!(sequencer_sets[2].block_commitment(), Some(&submitted));
+    assert_ne!(sequencer_sets[2].seed(), sequencer_sets[1].next_seed());
+
+    assert!(sequencer_sets[0].verify_seed(&rollup_id, None));
+    assert!(sequencer_sets[1].verify_seed(&rollup_id, Some(&sequencer_sets[0])));
+    assert!(sequencer_sets[2].verify_seed(&rollup_id, Some(&sequencer_sets[1])));
+    assert!(!sequencer_sets[2].verify_seed(&rollup_id, Some(&sequencer_sets[0])));
+    assert!(!sequencer_sets[1].verify_seed(&rollup_id, None));
+}
+
+#[tokio::test(crate = "ssal_core::tokio")]
+async fn a_quorum_of_followers_stands_in_for_a_withholding_leader() {
+    let (_database_dir, state) = common::app_state();
+    let rollup_id = RollupId::from("1");
+    common::register_rollup(&state, &rollup_id, ElectionMethod::Random).await;
+    let sequencers: Vec<Sequencer> = (8000..8004).map(Sequencer::new).collect();
+    for sequencer in sequencers.iter() {
+        sequencer.register(&state, &rollup_id, 0).await.unwrap();
+    }
+    common::close_block(&state, &rollup_id).await.unwrap();
+
+    let sequencer_set = closed_sequencer_set(&state, &rollup_id, 1);
+    let leader_id = sequencer_set.leader().unwrap();
+    let followers: Vec<&Sequencer> = sequencers
+        .iter()
+        .filter(|sequencer| sequencer.sequencer_id != leader_id)
+        .collect();
+    assert_eq!(sequencer_set.failover_quorum(), 2);
+    let block_commitment_key = ("block_commitment", &rollup_id, &BlockHeight::from(1));
+
+    // A follower endorses a single commitment, and alone does not make it final.
+    let submitted = block_commitment(&[1, 2, 3]);
+    submit(&state, &rollup_id, followers[0], &submitted)
+        .await
+        .unwrap();
+    let error = submit(
+        &state,
+        &rollup_id,
+        followers[0],
+        &block_commitment(&[4, 5, 6]),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidRequest);
+    submit(
+        &state,
+        &rollup_id,
+        followers[1],
+        &block_commitment(&[4, 5, 6]),
+    )
+    .await
+    .unwrap();
+    assert!(state
+        .database()
+        .get::<_, BlockCommitment>(&block_commitment_key)
+        .is_err());
+    let error = endorsed_block_commitment(&state, &rollup_id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::NotFound);
+
+    submit(&state, &rollup_id, followers[2], &submitted)
+        .await
+        .unwrap();
+    let stored: BlockCommitment = state.database().get(&block_commitment_key).unwrap();
+    assert_eq!(stored, submitted);
+
+    // The final commitment is served with the signatures which make it final.
+    let endorsed = endorsed_block_commitment(&state, &rollup_id).await.unwrap();
+    assert_eq!(*endorsed.block_commitment(), submitted);
+    assert!(endorsed.is_final(
+        &sequencer_set,
+        SubmitBlockCommitment::digest(&rollup_id, &BlockHeight::from(1), &submitted),
+    ));
+
+    // The leader cannot replace the commitment afterwards.
+    let leader = sequencers
+        .iter()
+        .find(|sequencer| sequencer.sequencer_id == leader_id)
+        .unwrap();
+    let error = submit(&state, &rollup_id, leader, &block_commitment(&[4, 5, 6]))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidRequest);
+}